@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use spider_utils::{
+    build_selectors, css_query_select_map, css_query_select_map_streamed, QueryCSSMap,
+    QueryCSSSelectSet,
+};
+
+/// bench extracting a single selector from a large document
+pub fn bench_single_selector(c: &mut Criterion) {
+    let html = format!(
+        "<html><head><title>Bench</title></head><body>{}</body></html>",
+        r#"<div class="product"><h2 class="title">Product</h2><span class="price">$10</span></div>"#
+            .repeat(1000)
+    );
+    let selectors = build_selectors(QueryCSSMap::from([(
+        "price",
+        QueryCSSSelectSet::from([".price"]),
+    )]));
+
+    let mut group = c.benchmark_group("selectors/single");
+    let rt = spider::tokio::runtime::Runtime::new().unwrap();
+
+    group.bench_function("css_query_select_map_streamed", |b| {
+        b.to_async(&rt)
+            .iter(|| css_query_select_map_streamed(black_box(&html), &selectors))
+    });
+
+    group.bench_function("css_query_select_map", |b| {
+        b.iter(|| css_query_select_map(black_box(&html), &selectors))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_single_selector);
+criterion_main!(benches);
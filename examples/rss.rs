@@ -9,6 +9,7 @@ use tokio::io::AsyncWriteExt;
 async fn main() {
     let mut website: Website = Website::new("https://a11ywatch.com/rss")
         .with_limit(5)
+        .with_feed_parsing(true)
         .build()
         .unwrap();
     let mut rx2: tokio::sync::broadcast::Receiver<spider::page::Page> =
@@ -90,6 +90,37 @@ pub fn parse_cookies_with_jar(cookie_str: &str, url: &Url) -> Result<Vec<CookieP
 pub async fn set_cookies(config: &Configuration, url_parsed: &Option<Box<Url>>, browser: &Browser) {
 }
 
+/// Handle the browser cookie configurations for the browser context.
+#[cfg(not(feature = "cookies"))]
+async fn set_context_cookies(
+    _config: &Configuration,
+    _url_parsed: &Option<Box<Url>>,
+    _browser: &Browser,
+    _context_id: &BrowserContextId,
+) {
+}
+
+/// Handle the browser cookie configurations for the browser context.
+#[cfg(feature = "cookies")]
+async fn set_context_cookies(
+    config: &Configuration,
+    url_parsed: &Option<Box<Url>>,
+    browser: &Browser,
+    context_id: &BrowserContextId,
+) {
+    use chromiumoxide::cdp::browser_protocol::storage::SetCookiesParams;
+
+    if !config.cookie_str.is_empty() {
+        if let Some(parsed) = url_parsed {
+            if let Ok(cookies) = parse_cookies_with_jar(&config.cookie_str, &*parsed) {
+                let mut cmd = SetCookiesParams::new(cookies);
+                cmd.browser_context_id = Some(context_id.clone());
+                let _ = browser.execute(cmd).await;
+            }
+        }
+    }
+}
+
 /// Handle the browser cookie configurations.
 #[cfg(feature = "cookies")]
 pub async fn set_cookies(config: &Configuration, url_parsed: &Option<Box<Url>>, browser: &Browser) {
@@ -141,7 +172,7 @@ pub fn get_browser_config(
                     if p.ignore == crate::configuration::ProxyIgnore::Chrome {
                         None
                     } else {
-                        Some(p.addr.to_owned())
+                        Some(p.chrome_addr())
                     }
                 })
                 .collect::<Vec<String>>();
@@ -218,7 +249,7 @@ pub fn get_browser_config(
                     if p.ignore == crate::configuration::ProxyIgnore::Chrome {
                         None
                     } else {
-                        Some(p.addr.to_owned())
+                        Some(p.chrome_addr())
                     }
                 })
                 .collect::<Vec<String>>();
@@ -254,7 +285,7 @@ fn create_handler_config(config: &Configuration) -> HandlerConfig {
             _ => Duration::from_millis(REQUEST_TIMEOUT),
         },
         request_intercept: config.chrome_intercept.enabled,
-        cache_enabled: config.cache,
+        cache_enabled: config.chrome_cache_enabled(),
         service_worker_enabled: config.service_worker_enabled,
         viewport: match config.viewport {
             Some(ref v) => Some(chromiumoxide::handler::viewport::Viewport::from(
@@ -354,7 +385,7 @@ pub async fn setup_browser_configuration(
         _ => match get_browser_config(
             &proxies,
             config.chrome_intercept.enabled,
-            config.cache,
+            config.chrome_cache_enabled(),
             match config.viewport {
                 Some(ref v) => Some(chromiumoxide::handler::viewport::Viewport::from(
                     v.to_owned(),
@@ -410,95 +441,191 @@ pub async fn launch_browser(
     tokio::task::JoinHandle<()>,
     Option<BrowserContextId>,
 )> {
-    use chromiumoxide::{
-        cdp::browser_protocol::target::CreateBrowserContextParams, error::CdpError,
-    };
-
     let browser_configuration = setup_browser_configuration(&config).await;
 
     match browser_configuration {
         Some(c) => {
-            let (mut browser, mut handler) = c;
+            let (mut browser, handler) = c;
             let mut context_id = None;
+            let handle = spawn_browser_handler(handler);
+            let create_content = browser_context_params(config);
 
-            // Spawn a new task that continuously polls the handler
-            // we might need a select with closing in case handler stalls.
-            let handle = tokio::task::spawn(async move {
-                while let Some(k) = handler.next().await {
-                    if let Err(e) = k {
-                        match e {
-                            CdpError::Ws(_)
-                            | CdpError::LaunchExit(_, _)
-                            | CdpError::LaunchTimeout(_)
-                            | CdpError::LaunchIo(_, _) => {
-                                break;
-                            }
-                            _ => {
-                                continue;
-                            }
-                        }
-                    }
+            if let Ok(c) = browser.create_browser_context(create_content).await {
+                let _ = browser.send_new_context(c.clone()).await;
+                let _ = context_id.insert(c);
+                set_cookies(&config, &url_parsed, &browser).await;
+                if let Some(id) = &browser.browser_context.id {
+                    deny_downloads(&browser, id).await;
                 }
-            });
-
-            let mut create_content = CreateBrowserContextParams::default();
-            create_content.dispose_on_detach = Some(true);
+            } else {
+                handle.abort();
+            }
 
-            if let Some(ref proxies) = config.proxies {
-                let use_plain_http = proxies.len() >= 2;
+            Some((browser, handle, context_id))
+        }
+        _ => None,
+    }
+}
 
-                for proxie in proxies.iter() {
-                    if proxie.ignore == crate::configuration::ProxyIgnore::Chrome {
+/// Spawn a new task that continuously polls the handler of the browser.
+fn spawn_browser_handler(mut handler: chromiumoxide::Handler) -> JoinHandle<()> {
+    // we might need a select with closing in case handler stalls.
+    tokio::task::spawn(async move {
+        while let Some(k) = handler.next().await {
+            if let Err(e) = k {
+                match e {
+                    CdpError::Ws(_)
+                    | CdpError::LaunchExit(_, _)
+                    | CdpError::LaunchTimeout(_)
+                    | CdpError::LaunchIo(_, _) => {
+                        break;
+                    }
+                    _ => {
                         continue;
                     }
+                }
+            }
+        }
+    })
+}
 
-                    let proxie = &proxie.addr;
+/// The browser context params with the proxy of the configuration.
+fn browser_context_params(
+    config: &Configuration,
+) -> chromiumoxide::cdp::browser_protocol::target::CreateBrowserContextParams {
+    let mut create_content =
+        chromiumoxide::cdp::browser_protocol::target::CreateBrowserContextParams::default();
+    create_content.dispose_on_detach = Some(true);
 
-                    if !proxie.is_empty() {
-                        // pick the socks:// proxy over http if found.
-                        if proxie.starts_with("socks://") {
-                            create_content.proxy_server =
-                                Some(proxie.replacen("socks://", "http://", 1).into());
-                            // pref this connection
-                            if use_plain_http {
-                                break;
-                            }
-                        }
+    if let Some(ref proxies) = config.proxies {
+        let use_plain_http = proxies.len() >= 2;
 
-                        if *LOOP_BACK_PROXY && proxie.starts_with("http://localhost") {
-                            create_content.proxy_bypass_list =
-                                    // https://source.chromium.org/chromium/chromium/src/+/main:net/proxy_resolution/proxy_bypass_rules.cc
-                                    Some("<-loopback>;localhost;[::1]".into());
-                        }
+        for proxie in proxies.iter() {
+            if proxie.ignore == crate::configuration::ProxyIgnore::Chrome {
+                continue;
+            }
+
+            let proxie = &proxie.chrome_addr();
 
-                        create_content.proxy_server = Some(proxie.into());
+            if !proxie.is_empty() {
+                // pick the socks:// proxy over http if found.
+                if proxie.starts_with("socks://") {
+                    create_content.proxy_server =
+                        Some(proxie.replacen("socks://", "http://", 1).into());
+                    // pref this connection
+                    if use_plain_http {
+                        break;
                     }
                 }
-            }
 
-            if let Ok(c) = browser.create_browser_context(create_content).await {
-                let _ = browser.send_new_context(c.clone()).await;
-                let _ = context_id.insert(c);
-                set_cookies(&config, &url_parsed, &browser).await;
-                if let Some(id) = &browser.browser_context.id {
-                    let cmd = SetDownloadBehaviorParamsBuilder::default();
-
-                    if let Ok(cmd) = cmd
-                        .behavior(SetDownloadBehaviorBehavior::Deny)
-                        .events_enabled(false)
-                        .browser_context_id(id.clone())
-                        .build()
-                    {
-                        let _ = browser.execute(cmd).await;
-                    }
+                if *LOOP_BACK_PROXY && proxie.starts_with("http://localhost") {
+                    create_content.proxy_bypass_list =
+                            // https://source.chromium.org/chromium/chromium/src/+/main:net/proxy_resolution/proxy_bypass_rules.cc
+                            Some("<-loopback>;localhost;[::1]".into());
                 }
-            } else {
-                handle.abort();
+
+                create_content.proxy_server = Some(proxie.into());
             }
+        }
+    }
 
-            Some((browser, handle, context_id))
+    create_content
+}
+
+/// Deny the downloads of the browser context.
+async fn deny_downloads(browser: &Browser, context_id: &BrowserContextId) {
+    let cmd = SetDownloadBehaviorParamsBuilder::default();
+
+    if let Ok(cmd) = cmd
+        .behavior(SetDownloadBehaviorBehavior::Deny)
+        .events_enabled(false)
+        .browser_context_id(context_id.clone())
+        .build()
+    {
+        let _ = browser.execute(cmd).await;
+    }
+}
+
+/// Aborts the handler of the shared browser when the last handle is dropped.
+struct SharedBrowserHandler(JoinHandle<()>);
+
+impl Drop for SharedBrowserHandler {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// A browser launched or connected once and shared across crawls, ex: many small crawls in a service. Each crawl runs in a new browser context for isolation that is disposed when the crawl finishes. Clones share the same browser and the browser closes when the last clone is dropped.
+#[derive(Clone)]
+pub struct SharedBrowser {
+    /// The browser.
+    browser: std::sync::Arc<Browser>,
+    /// The handler of the browser.
+    handler: std::sync::Arc<SharedBrowserHandler>,
+}
+
+impl std::fmt::Debug for SharedBrowser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedBrowser")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl PartialEq for SharedBrowser {
+    fn eq(&self, other: &Self) -> bool {
+        std::sync::Arc::ptr_eq(&self.browser, &other.browser)
+    }
+}
+
+impl SharedBrowser {
+    /// Launch or connect to the browser with the chrome settings of the configuration, ex: `chrome_connection_url`.
+    pub async fn launch(config: &Configuration) -> Option<Self> {
+        let (browser, handler) = setup_browser_configuration(config).await?;
+
+        Some(Self {
+            browser: std::sync::Arc::new(browser),
+            handler: std::sync::Arc::new(SharedBrowserHandler(spawn_browser_handler(handler))),
+        })
+    }
+
+    /// The connection to the browser closed, ex: the chrome process crashed.
+    pub fn is_closed(&self) -> bool {
+        self.handler.0.is_finished()
+    }
+
+    /// Create a new browser context for the crawl.
+    pub(crate) async fn new_controller(
+        &self,
+        config: &Configuration,
+        url_parsed: &Option<Box<Url>>,
+    ) -> Option<BrowserController> {
+        if self.is_closed() {
+            log::error!("the shared browser is closed.");
+            return None;
         }
-        _ => None,
+
+        let context_id = self
+            .browser
+            .execute(browser_context_params(config))
+            .await
+            .ok()?
+            .result
+            .browser_context_id;
+
+        tokio::join!(
+            set_context_cookies(config, url_parsed, &self.browser, &context_id),
+            deny_downloads(&self.browser, &context_id)
+        );
+
+        let mut controller = BrowserController::new(
+            (self.browser.clone(), None, Some(context_id)),
+            config.chrome_nav_concurrency,
+        );
+
+        controller.shared = true;
+
+        Some(controller)
     }
 }
 
@@ -594,6 +721,16 @@ pub async fn detect_geo_info(new_page: &Page) -> Option<GeoInfo> {
 
 /// configure the browser.
 pub async fn configure_browser(new_page: &Page, configuration: &Configuration) {
+    if let Some(network_conditions) = configuration.network_conditions {
+        let _ = new_page
+            .execute(
+                chromiumoxide::cdp::browser_protocol::network::EmulateNetworkConditionsParams::from(
+                    network_conditions,
+                ),
+            )
+            .await;
+    }
+
     let mut timezone = configuration.timezone_id.is_some();
     let mut locale = configuration.locale.is_some();
 
@@ -756,45 +893,60 @@ pub async fn close_browser(
     }
 }
 
-/// Setup interception for auth challenges. This does nothing without the 'chrome_intercept' flag.
+/// Setup interception for auth challenges. The proxy challenges are answered with the proxy credentials when set. This does nothing without the 'chrome_intercept' flag.
 #[cfg(all(feature = "chrome"))]
 pub async fn setup_auth_challenge_response(
     page: &chromiumoxide::Page,
     chrome_intercept: bool,
     auth_challenge_response: &Option<crate::configuration::AuthChallengeResponse>,
+    proxy_credentials: &Option<(String, String)>,
 ) {
-    if chrome_intercept {
-        if let Some(ref auth_challenge_response) = auth_challenge_response {
-            if let Ok(mut rp) = page
-                .event_listener::<chromiumoxide::cdp::browser_protocol::fetch::EventAuthRequired>()
-                .await
-            {
-                let intercept_page = page.clone();
-                let auth_challenge_response = auth_challenge_response.clone();
-
-                // we may need return for polling
-                crate::utils::spawn_task("auth_interception", async move {
-                    while let Some(event) = rp.next().await {
-                        let u = &event.request.url;
-                        let acr = chromiumoxide::cdp::browser_protocol::fetch::AuthChallengeResponse::from(auth_challenge_response.clone());
-
-                        match chromiumoxide::cdp::browser_protocol::fetch::ContinueWithAuthParams::builder()
-                        .request_id(event.request_id.clone())
-                        .auth_challenge_response(acr)
-                        .build() {
-                            Ok(c) => {
-                                if let Err(e) = intercept_page.send_command(c).await
-                                {
-                                    log("Failed to fullfill auth challege request: ", e.to_string());
-                                }
+    use chromiumoxide::cdp::browser_protocol::fetch::AuthChallengeSource;
+
+    if chrome_intercept && (auth_challenge_response.is_some() || proxy_credentials.is_some()) {
+        if let Ok(mut rp) = page
+            .event_listener::<chromiumoxide::cdp::browser_protocol::fetch::EventAuthRequired>()
+            .await
+        {
+            let intercept_page = page.clone();
+            let auth_challenge_response = auth_challenge_response.clone().unwrap_or_default();
+            let proxy_credentials = proxy_credentials.clone();
+
+            // we may need return for polling
+            crate::utils::spawn_task("auth_interception", async move {
+                while let Some(event) = rp.next().await {
+                    let u = &event.request.url;
+                    let acr = match (&proxy_credentials, &event.auth_challenge.source) {
+                        (Some((username, password)), Some(AuthChallengeSource::Proxy)) => {
+                            crate::configuration::AuthChallengeResponse {
+                                response: crate::configuration::AuthChallengeResponseResponse::ProvideCredentials,
+                                username: Some(username.clone()),
+                                password: Some(password.clone()),
                             }
-                            _ => {
-                                log("Failed to get auth challege request handle ", &u);
+                        }
+                        _ => auth_challenge_response.clone(),
+                    };
+                    let acr =
+                        chromiumoxide::cdp::browser_protocol::fetch::AuthChallengeResponse::from(
+                            acr,
+                        );
+
+                    match chromiumoxide::cdp::browser_protocol::fetch::ContinueWithAuthParams::builder()
+                    .request_id(event.request_id.clone())
+                    .auth_challenge_response(acr)
+                    .build() {
+                        Ok(c) => {
+                            if let Err(e) = intercept_page.send_command(c).await
+                            {
+                                log("Failed to fullfill auth challege request: ", e.to_string());
                             }
                         }
+                        _ => {
+                            log("Failed to get auth challege request handle ", &u);
+                        }
                     }
-                });
-            }
+                }
+            });
         }
     }
 }
@@ -805,11 +957,18 @@ pub async fn setup_chrome_interception_base(
     page: &chromiumoxide::Page,
     chrome_intercept: bool,
     auth_challenge_response: &Option<crate::configuration::AuthChallengeResponse>,
+    proxy_credentials: Option<(String, String)>,
     _ignore_visuals: bool,
     _host_name: &str,
 ) -> Option<tokio::task::JoinHandle<()>> {
     if chrome_intercept {
-        setup_auth_challenge_response(page, chrome_intercept, auth_challenge_response).await;
+        setup_auth_challenge_response(
+            page,
+            chrome_intercept,
+            auth_challenge_response,
+            &proxy_credentials,
+        )
+        .await;
     }
     None
 }
@@ -824,7 +983,7 @@ pub async fn setup_chrome_events(chrome_page: &chromiumoxide::Page, config: &Con
         spider_fingerprint::EmulationConfiguration::setup_defaults(ua_for_profiles);
 
     let stealth_mode = config.stealth_mode;
-    let use_stealth = stealth_mode.stealth();
+    let use_stealth = stealth_mode.stealth() || config.stealth_config.is_some();
     let block_ads = config.chrome_intercept.block_ads;
 
     emulation_config.dismiss_dialogs = config.dismiss_dialogs.unwrap_or(true);
@@ -846,6 +1005,15 @@ pub async fn setup_chrome_events(chrome_page: &chromiumoxide::Page, config: &Con
         &gpu_profile,
     );
 
+    // the individual evasions toggle the evasions of the tier.
+    let merged_script = match config.stealth_config {
+        Some(stealth_config) => Some(stealth_config.apply(
+            ua_for_profiles,
+            merged_script.as_deref().unwrap_or_default(),
+        )),
+        _ => merged_script,
+    };
+
     let should_inject_script =
         (use_stealth || config.evaluate_on_new_document.is_some()) && merged_script.is_some();
 
@@ -916,6 +1084,16 @@ pub(crate) type BrowserControl = (
     Option<chromiumoxide::cdp::browser_protocol::browser::BrowserContextId>,
 );
 
+/// Wait for a navigation permit when the navigations are limited. The permit is held until the page is rendered.
+pub(crate) async fn acquire_navigation(
+    navigations: &Option<std::sync::Arc<tokio::sync::Semaphore>>,
+) -> Option<tokio::sync::OwnedSemaphorePermit> {
+    match navigations {
+        Some(navigations) => navigations.clone().acquire_owned().await.ok(),
+        _ => None,
+    }
+}
+
 /// Once cell browser
 #[cfg(feature = "smart")]
 pub(crate) type OnceBrowser = tokio::sync::OnceCell<Option<BrowserController>>;
@@ -926,16 +1104,27 @@ pub struct BrowserController {
     pub browser: BrowserControl,
     /// Closed browser.
     pub closed: bool,
+    /// The permits of the concurrent navigations when limited.
+    pub(crate) navigations: Option<std::sync::Arc<tokio::sync::Semaphore>>,
+    /// The browser is shared across crawls and only the browser context is disposed.
+    shared: bool,
 }
 
 impl BrowserController {
-    /// A new browser controller.
-    pub(crate) fn new(browser: BrowserControl) -> Self {
+    /// A new browser controller limiting the concurrent navigations.
+    pub(crate) fn new(browser: BrowserControl, nav_concurrency: Option<usize>) -> Self {
         BrowserController {
             browser,
             closed: false,
+            navigations: nav_concurrency
+                .map(|limit| std::sync::Arc::new(tokio::sync::Semaphore::new(limit))),
+            shared: false,
         }
     }
+    /// Determine if the browser connection dropped without being disposed, ex: the chrome process crashed.
+    pub fn is_disconnected(&self) -> bool {
+        !self.closed && self.browser.1.as_ref().is_some_and(|h| h.is_finished())
+    }
     /// Dispose the browser context and join handler.
     pub fn dispose(&mut self) {
         if !self.closed {
@@ -943,6 +1132,22 @@ impl BrowserController {
             if let Some(handler) = self.browser.1.take() {
                 handler.abort();
             }
+            if self.shared {
+                if let (Some(context_id), Ok(runtime)) =
+                    (self.browser.2.take(), tokio::runtime::Handle::try_current())
+                {
+                    let browser = self.browser.0.clone();
+                    runtime.spawn(async move {
+                        let _ = browser
+                            .execute(
+                                chromiumoxide::cdp::browser_protocol::target::DisposeBrowserContextParams::new(
+                                    context_id,
+                                ),
+                            )
+                            .await;
+                    });
+                }
+            }
         }
     }
 }
@@ -241,6 +241,226 @@ impl From<Viewport> for spider_fingerprint::spoof_viewport::Viewport {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Network conditions to emulate for chrome, ex: a throttled mobile network.
+pub struct NetworkConditions {
+    /// Emulate the browser being offline.
+    pub offline: bool,
+    /// Minimum latency from the request sent to the response headers received in milliseconds.
+    pub latency: f64,
+    /// Maximal download throughput in bytes per second. Use `-1.0` to disable throttling.
+    pub download_throughput: f64,
+    /// Maximal upload throughput in bytes per second. Use `-1.0` to disable throttling.
+    pub upload_throughput: f64,
+}
+
+impl Default for NetworkConditions {
+    fn default() -> Self {
+        NetworkConditions {
+            offline: false,
+            latency: 0.0,
+            download_throughput: -1.0,
+            upload_throughput: -1.0,
+        }
+    }
+}
+
+impl NetworkConditions {
+    /// Create new network conditions with the latency in milliseconds and the throughput in bytes per second.
+    pub fn new(latency: f64, download_throughput: f64, upload_throughput: f64) -> Self {
+        NetworkConditions {
+            offline: false,
+            latency,
+            download_throughput,
+            upload_throughput,
+        }
+    }
+    /// The browser is offline.
+    pub fn offline() -> Self {
+        NetworkConditions {
+            offline: true,
+            ..Default::default()
+        }
+    }
+    /// The chrome devtools "Slow 3G" preset.
+    pub fn slow_3g() -> Self {
+        Self::new(2000.0, 50_000.0, 50_000.0)
+    }
+    /// The chrome devtools "Fast 3G" preset.
+    pub fn fast_3g() -> Self {
+        Self::new(562.5, 180_000.0, 84_375.0)
+    }
+}
+
+#[cfg(feature = "chrome")]
+impl From<NetworkConditions>
+    for chromiumoxide::cdp::browser_protocol::network::EmulateNetworkConditionsParams
+{
+    fn from(conditions: NetworkConditions) -> Self {
+        Self::new(
+            conditions.offline,
+            conditions.latency,
+            conditions.download_throughput,
+            conditions.upload_throughput,
+        )
+    }
+}
+
+/// Remove the `navigator.webdriver` flag set by automation.
+const EVASION_WEBDRIVER: &str = "(()=>{Object.defineProperty(Navigator.prototype,'webdriver',{get:()=>false,configurable:true})})();";
+
+/// Spoof the PDF viewer plugins of a desktop chrome when none are exposed.
+const EVASION_PLUGINS: &str = "(()=>{if(navigator.plugins.length)return;const p=['PDF Viewer','Chrome PDF Viewer','Chromium PDF Viewer','Microsoft Edge PDF Viewer','WebKit built-in PDF'].map(n=>({name:n,filename:'internal-pdf-viewer',description:'Portable Document Format',length:0}));Object.defineProperty(Navigator.prototype,'plugins',{get:()=>p,configurable:true})})();";
+
+/// Report a common GPU of the platform for the unmasked WebGL vendor and renderer instead of the software renderer of headless chrome.
+fn evasion_webgl_vendor(user_agent: &str) -> String {
+    let (vendor, renderer) = webgl_vendor(user_agent);
+
+    format!("(()=>{{for(const c of [self.WebGLRenderingContext,self.WebGL2RenderingContext]){{if(!c)continue;const g=c.prototype.getParameter;c.prototype.getParameter=function(p){{if(p===37445)return'{vendor}';if(p===37446)return'{renderer}';return g.call(this,p)}}}}}})();")
+}
+
+/// The unmasked WebGL vendor and renderer of a common GPU for the platform of the user agent, or of the host when the user agent is empty.
+fn webgl_vendor(user_agent: &str) -> (&'static str, &'static str) {
+    let platform = if user_agent.is_empty() {
+        match std::env::consts::OS {
+            "macos" => "Macintosh",
+            "linux" => "Linux",
+            _ => "Windows",
+        }
+    } else {
+        user_agent
+    };
+
+    if platform.contains("Android") {
+        ("Qualcomm", "Adreno (TM) 650")
+    } else if platform.contains("iPhone") || platform.contains("iPad") {
+        ("Apple Inc.", "Apple GPU")
+    } else if platform.contains("Macintosh") {
+        (
+            "Google Inc. (Apple)",
+            "ANGLE (Apple, ANGLE Metal Renderer: Apple M1, Unspecified Version)",
+        )
+    } else if platform.contains("CrOS") {
+        (
+            "Google Inc. (Intel)",
+            "ANGLE (Intel, Mesa Intel(R) UHD Graphics 620 (KBL GT2), OpenGL ES 3.2)",
+        )
+    } else if platform.contains("Linux") {
+        (
+            "Google Inc. (AMD)",
+            "ANGLE (AMD, AMD Radeon Graphics (radeonsi, renoir, LLVM 15.0.7), OpenGL 4.6)",
+        )
+    } else {
+        (
+            "Google Inc. (NVIDIA)",
+            "ANGLE (NVIDIA, NVIDIA GeForce GTX 1650 Direct3D11 vs_5_0 ps_5_0, D3D11)",
+        )
+    }
+}
+
+/// The native `navigator.webdriver` restored when the evasion is disabled.
+const NATIVE_WEBDRIVER: &str =
+    "[self.Navigator&&Navigator.prototype,'webdriver'],[self.navigator,'webdriver'],";
+/// The native `navigator.plugins` restored when the evasion is disabled.
+const NATIVE_PLUGINS: &str =
+    "[self.Navigator&&Navigator.prototype,'plugins'],[self.navigator,'plugins'],";
+/// The native WebGL `getParameter` restored when the evasion is disabled.
+const NATIVE_WEBGL_VENDOR: &str = "[self.WebGLRenderingContext&&WebGLRenderingContext.prototype,'getParameter'],[self.WebGL2RenderingContext&&WebGL2RenderingContext.prototype,'getParameter'],";
+/// The native canvas readers restored when the evasion is disabled.
+const NATIVE_CANVAS_NOISE: &str = "[self.CanvasRenderingContext2D&&CanvasRenderingContext2D.prototype,'getImageData'],[self.HTMLCanvasElement&&HTMLCanvasElement.prototype,'toDataURL'],[self.HTMLCanvasElement&&HTMLCanvasElement.prototype,'toBlob'],";
+/// The native `chrome` object restored when the evasion is disabled.
+const NATIVE_CHROME_RUNTIME: &str = "[self,'chrome'],[self.chrome,'runtime'],";
+
+/// Add noise to the pixels read from the canvas to vary the canvas fingerprint.
+const EVASION_CANVAS_NOISE: &str = "(()=>{const c=self.CanvasRenderingContext2D;if(!c)return;const g=c.prototype.getImageData;c.prototype.getImageData=function(...a){const d=g.apply(this,a);for(let i=0;i<d.data.length;i+=97)d.data[i]^=1;return d};const t=HTMLCanvasElement.prototype.toDataURL;HTMLCanvasElement.prototype.toDataURL=function(...a){const x=this.width&&this.height&&this.getContext('2d');if(x)x.putImageData(x.getImageData(0,0,1,1),0,0);return t.apply(this,a)}})();";
+
+/// Add the `chrome.runtime` object missing from headless chrome.
+const EVASION_CHROME_RUNTIME: &str = "(()=>{self.chrome=self.chrome||{};if(!self.chrome.runtime)self.chrome.runtime={connect(){},sendMessage(){},id:undefined}})();";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Toggle the stealth evasions of the pages over the evasions of the stealth tier, ex: disable the canvas noise for a site that draws with the canvas. The evasions enabled are applied even without a stealth tier, and the evasions disabled keep the native browser apis. All of the evasions are enabled by default.
+pub struct StealthConfig {
+    /// Remove the `navigator.webdriver` flag.
+    pub webdriver: bool,
+    /// Spoof the browser plugins.
+    pub plugins: bool,
+    /// Spoof the WebGL vendor and renderer.
+    pub webgl_vendor: bool,
+    /// Add noise to the canvas fingerprint.
+    pub canvas_noise: bool,
+    /// Add the `chrome.runtime` shim.
+    pub chrome_runtime: bool,
+}
+
+impl Default for StealthConfig {
+    fn default() -> Self {
+        StealthConfig {
+            webdriver: true,
+            plugins: true,
+            webgl_vendor: true,
+            canvas_noise: true,
+            chrome_runtime: true,
+        }
+    }
+}
+
+impl StealthConfig {
+    /// No evasions enabled to opt in to each of them.
+    pub fn none() -> Self {
+        StealthConfig {
+            webdriver: false,
+            plugins: false,
+            webgl_vendor: false,
+            canvas_noise: false,
+            chrome_runtime: false,
+        }
+    }
+    /// The script applying the evasions enabled for the user agent.
+    pub fn script(&self, user_agent: &str) -> String {
+        let mut script: String = [
+            (self.webdriver, EVASION_WEBDRIVER),
+            (self.plugins, EVASION_PLUGINS),
+            (self.canvas_noise, EVASION_CANVAS_NOISE),
+            (self.chrome_runtime, EVASION_CHROME_RUNTIME),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, evasion)| evasion)
+        .collect();
+
+        if self.webgl_vendor {
+            script.push_str(&evasion_webgl_vendor(user_agent));
+        }
+
+        script
+    }
+
+    /// Toggle the evasions of the tier script, applying the evasions enabled after it and restoring the native apis patched by it for the evasions disabled.
+    pub fn apply(&self, user_agent: &str, tier_script: &str) -> String {
+        let natives: String = [
+            (self.webdriver, NATIVE_WEBDRIVER),
+            (self.plugins, NATIVE_PLUGINS),
+            (self.webgl_vendor, NATIVE_WEBGL_VENDOR),
+            (self.canvas_noise, NATIVE_CANVAS_NOISE),
+            (self.chrome_runtime, NATIVE_CHROME_RUNTIME),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| !*enabled)
+        .map(|(_, native)| native)
+        .collect();
+
+        let script = self.script(user_agent);
+
+        if natives.is_empty() || tier_script.is_empty() {
+            return tier_script.to_owned() + &script;
+        }
+
+        format!("{{const __spider_natives=[{natives}].filter(([o])=>o).map(([o,p])=>[o,p,Object.getOwnPropertyDescriptor(o,p)]);\n{tier_script}\n{script}for(const[o,p,d]of __spider_natives){{try{{d?Object.defineProperty(o,p,d):delete o[p]}}catch(e){{}}}}}}")
+    }
+}
+
 #[doc = "Capture page screenshot.\n[captureScreenshot](https://chromedevtools.github.io/devtools-protocol/tot/Page/#method-captureScreenshot)"]
 #[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -305,6 +305,13 @@ impl DatabaseHandler {
         Ok(urls)
     }
 
+    /// Close the pool waiting for the pending writes to finish.
+    pub async fn close(&self) {
+        if let Some(pool) = self.pool.get() {
+            pool.close().await;
+        }
+    }
+
     /// Clear DB by id
     pub fn delete_db_by_id(&self) {
         let _ = std::fs::remove_file(get_db_path(&self.crawl_id));
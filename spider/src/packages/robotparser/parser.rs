@@ -51,6 +51,8 @@ pub struct RuleLine {
 pub struct RuleLine {
     /// Path of the rule
     pub path: Option<regex::Regex>,
+    /// The raw path pattern of the rule with the `*` wildcards and `$` anchor.
+    pub pattern: String,
     /// Is the rule allowed?
     pub allowance: bool,
 }
@@ -116,10 +118,11 @@ impl RuleLine {
         use regex::Regex;
 
         RuleLine {
-            path: match Regex::new(path) {
+            path: match Regex::new(&robots_pattern_regex(path)) {
                 Ok(r) => Some(r),
                 _ => None,
             },
+            pattern: path.into(),
             allowance: path.is_empty() && !allowance || allowance,
         }
     }
@@ -134,17 +137,19 @@ impl RuleLine {
 
     #[cfg(not(feature = "regex"))]
     fn applies_to(&self, pathname: &str) -> bool {
-        if self.path == "*"
-            || self.path == "/" && pathname == "/"
-            || self.path.ends_with("/") && pathname.starts_with(&self.path)
-        {
-            true
-        } else {
-            self.path
-                .strip_suffix('*')
-                .map_or(false, |prefix| pathname.starts_with(prefix))
-                || pathname == self.path
-        }
+        !self.path.is_empty() && robots_pattern_matches(&self.path, pathname)
+    }
+
+    /// The raw path pattern of the rule.
+    #[cfg(not(feature = "regex"))]
+    fn pattern(&self) -> &str {
+        &self.path
+    }
+
+    /// The raw path pattern of the rule.
+    #[cfg(feature = "regex")]
+    fn pattern(&self) -> &str {
+        &self.pattern
     }
 
     #[cfg(feature = "regex")]
@@ -187,13 +192,24 @@ impl Entry {
     /// Preconditions:
     /// - our agent applies to this entry
     /// - filename is URL decoded
+    ///
+    /// The rule with the longest matching path wins and allow wins a tie.
     fn allowance(&self, filename: &str) -> bool {
+        let mut matched: Option<(usize, bool)> = None;
+
         for line in &self.rulelines {
             if line.applies_to(filename) {
-                return line.allowance;
+                let len = line.pattern().len();
+
+                match matched {
+                    Some((matched_len, allowance))
+                        if matched_len > len || matched_len == len && allowance => {}
+                    _ => matched = Some((len, line.allowance)),
+                }
             }
         }
-        true
+
+        matched.is_none_or(|(_, allowance)| allowance)
     }
 
     /// Add to user agent list
@@ -243,6 +259,65 @@ impl Default for Entry {
     }
 }
 
+/// Does the robots.txt path pattern match the path. The pattern matches the start of the path with `*` matching any characters and a trailing `$` matching the end of the path.
+#[cfg(not(feature = "regex"))]
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        _ => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+
+    let mut rest = match path.strip_prefix(parts.next().unwrap_or_default()) {
+        Some(rest) => rest,
+        _ => return false,
+    };
+
+    let parts: Vec<&str> = parts.collect();
+
+    if parts.is_empty() {
+        return !anchored || rest.is_empty();
+    }
+
+    for (i, part) in parts.iter().enumerate() {
+        if anchored && i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+
+        match rest.find(part) {
+            Some(start) => rest = &rest[start + part.len()..],
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+/// Convert the robots.txt path pattern to a regex matching the start of the path with `*` as any characters and a trailing `$` as the end of the path.
+#[cfg(feature = "regex")]
+fn robots_pattern_regex(pattern: &str) -> String {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        _ => (pattern, false),
+    };
+
+    let mut regex = String::from("^");
+
+    for (i, part) in pattern.split('*').enumerate() {
+        if i > 0 {
+            regex.push_str(".*");
+        }
+        regex.push_str(&regex::escape(part));
+    }
+
+    if anchored {
+        regex.push('$');
+    }
+
+    regex
+}
+
 /// extract the path of a string
 fn extract_path(url: &str) -> &str {
     if !url.is_empty() {
@@ -330,8 +405,28 @@ impl RobotFileParser {
         &self.default_entry
     }
 
-    /// Reads the robots.txt URL and feeds it to the parser.
-    pub async fn read(&mut self, client: &Client, url: &str) {
+    /// Get the entries parsed with the base entry last.
+    pub fn rules(&self) -> Vec<&Entry> {
+        let mut rules: Vec<&Entry> = self.entries.iter().collect();
+
+        if !self.default_entry.is_empty() {
+            rules.push(&self.default_entry);
+        }
+
+        rules
+    }
+
+    /// Feeds the robots.txt content to the parser without a request.
+    pub fn read_str(&mut self, robots_txt: &str) {
+        self.modified();
+
+        let lines: Vec<&str> = robots_txt.split('\n').collect();
+
+        self.parse(&lines);
+    }
+
+    /// Reads the robots.txt URL and feeds it to the parser. Returns false when the request failed or the server errored, the robots.txt should be fetched again.
+    pub async fn read(&mut self, client: &Client, url: &str) -> bool {
         use crate::client::StatusCode;
         self.modified();
 
@@ -340,7 +435,7 @@ impl RobotFileParser {
         let res = match request.send().await {
             Ok(res) => res,
             Err(_) => {
-                return;
+                return false;
             }
         };
         let status = res.status();
@@ -356,8 +451,10 @@ impl RobotFileParser {
                 self.allow_all = true;
             }
             StatusCode::OK => self.from_response(res).await,
-            _ => (),
+            _ => return false,
         }
+
+        true
     }
 
     /// Reads the HTTP response and feeds it to the parser.
@@ -506,7 +603,7 @@ impl RobotFileParser {
     #[cfg(feature = "regex")]
     pub fn set_disallow_list(&mut self, path: &str) {
         if !path.is_empty() {
-            self.disallow_paths.insert(path.into());
+            self.disallow_paths.insert(robots_pattern_regex(path));
         }
     }
 
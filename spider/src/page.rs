@@ -1,9 +1,10 @@
+use crate::configuration::PaginationConfig;
 #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
 use crate::configuration::{AutomationScripts, ExecutionScripts};
 use crate::utils::abs::convert_abs_path;
 use crate::utils::EMPTY_HTML_BASIC;
 use crate::utils::{
-    css_selectors::{BASE_CSS_SELECTORS, BASE_CSS_SELECTORS_WITH_XML},
+    css_selectors::{scoped_css_selector, BASE_CSS_SELECTORS, BASE_CSS_SELECTORS_WITH_XML},
     get_domain_from_url, hash_html, networking_capable, PageResponse, RequestError,
 };
 #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
@@ -378,41 +379,807 @@ impl Metadata {
     }
 }
 
-// /// Open Graph metadata extracted from `<meta property="og:*">` tags.
-// #[derive(Debug, Default, Clone)]
-// #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-// pub struct OpenGraph {
-//     /// The Open Graph title (`og:title`). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub title: Option<CompactString>,
-//     /// The Open Graph description (`og:description`). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub description: Option<CompactString>,
-//     /// The Open Graph image URL (`og:image`).
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub image: Option<CompactString>,
-//     /// The canonical page URL (`og:url`). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub url: Option<CompactString>,
-//     /// The content type (`og:type`, e.g., "article", "website"). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub content_type: Option<CompactString>,
-//     /// The site name (`og:site_name`). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub site_name: Option<CompactString>,
-//     /// The locale of the content (`og:locale`, e.g., "en_US"). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub locale: Option<CompactString>,
-//     /// The author's name (`article:author` or `og:author`). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub author: Option<CompactString>,
-//     /// The time the content was first published (`article:published_time`). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub published_time: Option<CompactString>,
-//     /// The time the content was last modified (`article:modified_time`). NOT USED.
-//     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-//     pub modified_time: Option<CompactString>,
-// }
+/// The metadata found in the `<head>` of the page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeadMetadata {
+    /// The `<title>` text from the page.
+    pub title: Option<CompactString>,
+    /// The `<meta name="description">` content.
+    pub description: Option<CompactString>,
+    /// The `<link rel="canonical">` href.
+    pub canonical: Option<CompactString>,
+    /// The Open Graph `<meta property="og:*">` and `<meta property="article:*">` tags in order as property and content.
+    pub open_graph: Vec<(CompactString, CompactString)>,
+    /// The Twitter Card `<meta name="twitter:*">` tags in order as name and content.
+    pub twitter: Vec<(CompactString, CompactString)>,
+    /// The `<link rel="icon">` and `<link rel="apple-touch-icon">` icons in order.
+    pub icons: Vec<HeadIcon>,
+}
+
+/// An icon declared in the `<head>` of the page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeadIcon {
+    /// The `rel` attribute of the link.
+    pub rel: CompactString,
+    /// The `href` attribute of the link.
+    pub href: CompactString,
+    /// The `sizes` attribute of the link.
+    pub sizes: Option<CompactString>,
+}
+
+impl HeadIcon {
+    /// Is the icon an apple touch icon.
+    pub fn is_apple_touch_icon(&self) -> bool {
+        self.rel.split_ascii_whitespace().any(|r| {
+            r.eq_ignore_ascii_case("apple-touch-icon")
+                || r.eq_ignore_ascii_case("apple-touch-icon-precomposed")
+        })
+    }
+
+    /// The largest size of the icon in pixels. The size `any` is treated as the largest.
+    pub fn largest_size(&self) -> u32 {
+        self.sizes
+            .as_deref()
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+            .filter_map(|size| {
+                if size.eq_ignore_ascii_case("any") {
+                    Some(u32::MAX)
+                } else {
+                    let (w, h) = size.split_once(['x', 'X'])?;
+                    Some(w.parse::<u32>().ok()?.max(h.parse().ok()?))
+                }
+            })
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Is the `rel` attribute of the link an icon.
+fn is_icon_rel(rel: &str) -> bool {
+    rel.split_ascii_whitespace().any(|r| {
+        r.eq_ignore_ascii_case("icon")
+            || r.eq_ignore_ascii_case("apple-touch-icon")
+            || r.eq_ignore_ascii_case("apple-touch-icon-precomposed")
+    })
+}
+
+/// Resolve the favicon of the page preferring the largest `icon` before the `apple-touch-icon` and falling back to `/favicon.ico`.
+pub(crate) fn resolve_favicon_url(icons: &[HeadIcon], base: &Url) -> Option<Url> {
+    let icon = icons
+        .iter()
+        .filter(|icon| !icon.href.trim().is_empty())
+        .max_by_key(|icon| (!icon.is_apple_touch_icon(), icon.largest_size()));
+
+    match icon {
+        Some(icon) => base.join(icon.href.trim()).ok(),
+        _ => base.join("/favicon.ico").ok(),
+    }
+}
+
+/// Parse the metadata found in the `<head>` of the html.
+pub(crate) fn parse_head_metadata(html: &[u8]) -> HeadMetadata {
+    let mut title = String::new();
+    let mut description = None;
+    let mut canonical = None;
+    let mut open_graph = Vec::new();
+    let mut twitter = Vec::new();
+    let mut icons = Vec::new();
+
+    let rewriter_settings = lol_html::Settings {
+        element_content_handlers: vec![
+            lol_html::text!("head title", |el| {
+                title.push_str(el.as_str());
+                Ok(())
+            }),
+            lol_html::element!(r#"meta[name="description"]"#, |el| {
+                if let Some(content) = el.get_attribute("content") {
+                    description = Some(content.into());
+                }
+                Ok(())
+            }),
+            lol_html::element!(r#"link[rel="canonical"]"#, |el| {
+                if let Some(href) = el.get_attribute("href") {
+                    canonical = Some(href.into());
+                }
+                Ok(())
+            }),
+            lol_html::element!(
+                r#"meta[property^="og:"], meta[property^="article:"]"#,
+                |el| {
+                    if let (Some(property), Some(content)) =
+                        (el.get_attribute("property"), el.get_attribute("content"))
+                    {
+                        open_graph.push((property.into(), content.into()));
+                    }
+                    Ok(())
+                }
+            ),
+            lol_html::element!(
+                r#"meta[name^="twitter:"], meta[property^="twitter:"]"#,
+                |el| {
+                    if let (Some(name), Some(content)) = (
+                        el.get_attribute("name").or(el.get_attribute("property")),
+                        el.get_attribute("content"),
+                    ) {
+                        twitter.push((name.into(), content.into()));
+                    }
+                    Ok(())
+                }
+            ),
+            lol_html::element!("link[rel][href]", |el| {
+                if let (Some(rel), Some(href)) = (el.get_attribute("rel"), el.get_attribute("href"))
+                {
+                    if is_icon_rel(&rel) {
+                        icons.push(HeadIcon {
+                            rel: rel.into(),
+                            href: href.into(),
+                            sizes: el.get_attribute("sizes").map(|s| s.into()),
+                        });
+                    }
+                }
+                Ok(())
+            }),
+        ],
+        adjust_charset_on_meta_tag: true,
+        ..lol_html::send::Settings::new_for_handler_types()
+    };
+
+    let mut rewriter = lol_html::send::HtmlRewriter::new(rewriter_settings, |_c: &[u8]| {});
+
+    if rewriter.write(html).is_ok() {
+        let _ = rewriter.end();
+    } else {
+        drop(rewriter);
+    }
+
+    let title = title.trim();
+
+    HeadMetadata {
+        title: if title.is_empty() {
+            None
+        } else {
+            Some(title.into())
+        },
+        description,
+        canonical,
+        open_graph,
+        twitter,
+        icons,
+    }
+}
+
+/// Resolve the `<base href>` of the page to the url of the page, ex: `<base href="/app/">`.
+pub(crate) fn resolve_base_href(href: &str, page_url: Option<&Url>) -> Option<Url> {
+    let href = href.trim();
+
+    match Url::parse(href) {
+        Ok(base) => Some(base),
+        Err(url::ParseError::RelativeUrlWithoutBase) => {
+            page_url.and_then(|url| url.join(href).ok())
+        }
+        _ => None,
+    }
+}
+
+/// Parse the `content` of a `<meta http-equiv="refresh">` tag into the delay in seconds and the url, ex: `0; url=/next`.
+pub(crate) fn parse_meta_refresh_content(content: &str) -> Option<(u32, &str)> {
+    let content = content.trim();
+    let split = content
+        .find(|c: char| c == ';' || c == ',' || c.is_ascii_whitespace())
+        .unwrap_or(content.len());
+    let (delay, rest) = content.split_at(split);
+
+    let delay = delay.split('.').next()?.parse::<u32>().ok()?;
+    let rest = rest.trim_start_matches(|c: char| c == ';' || c == ',' || c.is_ascii_whitespace());
+
+    let url = match rest.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url") => {
+            rest[3..].trim_start().strip_prefix('=')?.trim_start()
+        }
+        _ => rest,
+    };
+
+    let url = match url.chars().next() {
+        Some(quote @ ('"' | '\'')) => url[1..].split(quote).next().unwrap_or_default(),
+        _ => url,
+    }
+    .trim();
+
+    if url.is_empty() {
+        None
+    } else {
+        Some((delay, url))
+    }
+}
+
+/// The `<meta http-equiv="refresh">` redirect selector.
+const META_REFRESH_SELECTOR: &str = r#"meta[http-equiv="refresh" i][content]"#;
+
+/// Parse the first `<meta http-equiv="refresh">` redirect of the html resolving the url to the base.
+pub(crate) fn parse_meta_refresh(html: &[u8], base: &Url) -> Option<(u32, Url)> {
+    let refresh: std::sync::OnceLock<String> = Default::default();
+
+    let rewriter_settings = lol_html::Settings {
+        element_content_handlers: vec![lol_html::element!(META_REFRESH_SELECTOR, |el| {
+            if let Some(content) = el.get_attribute("content") {
+                let _ = refresh.set(content);
+            }
+            Ok(())
+        })],
+        adjust_charset_on_meta_tag: true,
+        ..lol_html::send::Settings::new_for_handler_types()
+    };
+
+    let mut rewriter = lol_html::send::HtmlRewriter::new(rewriter_settings, |_c: &[u8]| {});
+
+    if rewriter.write(html).is_ok() {
+        let _ = rewriter.end();
+    } else {
+        drop(rewriter);
+    }
+
+    let refresh = refresh.into_inner()?;
+    let (delay, url) = parse_meta_refresh_content(&refresh)?;
+
+    Some((delay, convert_abs_path(base, url)))
+}
+
+/// The mount points of the client side rendered apps.
+const APP_ROOT_SELECTORS: &str =
+    "div#app, div#root, div#__next, div#__nuxt, div#___gatsby, div[data-reactroot], app-root";
+
+/// The html is an empty shell that needs javascript to render the content, ex: a SPA with a `<div id="app">` root and little text. Use it to fetch with HTTP first and only render the page with chrome when required.
+pub fn needs_rendering(html: &str) -> bool {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    let body_text = AtomicUsize::new(0);
+    let hidden_text = AtomicUsize::new(0);
+    let scripts = AtomicUsize::new(0);
+    let app_root = AtomicBool::new(false);
+    let js_required = AtomicBool::new(false);
+
+    let visible_chars = |text: &str| text.chars().filter(|c| !c.is_whitespace()).count();
+
+    let rewriter_settings = lol_html::Settings {
+        element_content_handlers: vec![
+            lol_html::element!("script", |_el| {
+                scripts.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }),
+            lol_html::element!(APP_ROOT_SELECTORS, |_el| {
+                app_root.store(true, Ordering::Relaxed);
+                Ok(())
+            }),
+            lol_html::text!("body", |t| {
+                body_text.fetch_add(visible_chars(t.as_str()), Ordering::Relaxed);
+                Ok(())
+            }),
+            lol_html::text!(
+                "body script, body style, body noscript, body template",
+                |t| {
+                    hidden_text.fetch_add(visible_chars(t.as_str()), Ordering::Relaxed);
+                    Ok(())
+                }
+            ),
+            lol_html::text!("noscript", |t| {
+                if t.as_str().to_ascii_lowercase().contains("javascript") {
+                    js_required.store(true, Ordering::Relaxed);
+                }
+                Ok(())
+            }),
+        ],
+        ..lol_html::send::Settings::new_for_handler_types()
+    };
+
+    let mut rewriter = lol_html::send::HtmlRewriter::new(rewriter_settings, |_c: &[u8]| {});
+
+    if rewriter.write(html.as_bytes()).is_ok() {
+        let _ = rewriter.end();
+    } else {
+        drop(rewriter);
+    }
+
+    let text = body_text
+        .into_inner()
+        .saturating_sub(hidden_text.into_inner());
+    let scripts = scripts.into_inner();
+
+    if text < 50 {
+        scripts > 0
+    } else if text < 200 {
+        app_root.into_inner() || js_required.into_inner() || scripts >= 3
+    } else {
+        false
+    }
+}
+
+/// A link on the page with the context of the anchor.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Anchor {
+    /// The absolute `href` of the link.
+    pub href: CompactString,
+    /// The text of the anchor with the whitespace collapsed.
+    pub text: CompactString,
+    /// The `rel` attribute of the link, ex: `nofollow`.
+    pub rel: Option<CompactString>,
+    /// The `title` attribute of the link.
+    pub title: Option<CompactString>,
+}
+
+impl Anchor {
+    /// The `rel` attribute contains the value, ex: `nofollow`.
+    pub fn has_rel(&self, value: &str) -> bool {
+        self.rel
+            .as_deref()
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+            .any(|r| r.eq_ignore_ascii_case(value))
+    }
+}
+
+/// Parse the `<a href>` links of the html in order resolving the href to the base.
+pub(crate) fn parse_anchors(html: &[u8], base: Option<&Url>) -> Vec<Anchor> {
+    let anchors: std::sync::Mutex<Vec<(Anchor, String)>> = Default::default();
+    let base_href: std::sync::OnceLock<Url> = Default::default();
+
+    let rewriter_settings = lol_html::Settings {
+        element_content_handlers: vec![
+            lol_html::element!("base[href]", |el| {
+                if let Some(href) = el.get_attribute("href") {
+                    if let Some(parsed_base) = resolve_base_href(&href, base) {
+                        let _ = base_href.set(parsed_base);
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!("a[href]", |el| {
+                if let Some(href) = el.get_attribute("href") {
+                    let href = match base_href.get().or(base) {
+                        Some(base) => convert_abs_path(base, &href).as_str().into(),
+                        _ => href.trim().into(),
+                    };
+
+                    if let Ok(mut anchors) = anchors.lock() {
+                        anchors.push((
+                            Anchor {
+                                href,
+                                rel: el.get_attribute("rel").map(|r| r.into()),
+                                title: el.get_attribute("title").map(|t| t.into()),
+                                ..Default::default()
+                            },
+                            String::new(),
+                        ));
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::text!("a[href]", |el| {
+                if let Ok(mut anchors) = anchors.lock() {
+                    if let Some((_, text)) = anchors.last_mut() {
+                        text.push_str(el.as_str());
+                    }
+                }
+                Ok(())
+            }),
+        ],
+        adjust_charset_on_meta_tag: true,
+        ..lol_html::send::Settings::new_for_handler_types()
+    };
+
+    let mut rewriter = lol_html::send::HtmlRewriter::new(rewriter_settings, |_c: &[u8]| {});
+
+    if rewriter.write(html).is_ok() {
+        let _ = rewriter.end();
+    } else {
+        drop(rewriter);
+    }
+
+    anchors
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(mut anchor, text)| {
+            anchor.text = text.split_whitespace().collect::<Vec<_>>().join(" ").into();
+            anchor
+        })
+        .collect()
+}
+
+/// The elements loading a subresource of the page from the `src` attribute.
+const SUBRESOURCE_SRC_ELEMENTS: &str =
+    "img[src], script[src], iframe[src], embed[src], source[src], video[src], audio[src], track[src]";
+
+/// The links of the page classified by the host of the page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassifiedLinks {
+    /// The `<a href>` and `<area href>` links on the same host as the page.
+    pub internal: HashSet<CaseInsensitiveString>,
+    /// The `<a href>` and `<area href>` links on a different host than the page.
+    pub external: HashSet<CaseInsensitiveString>,
+    /// The images, scripts, stylesheets, frames, and media loaded by the page.
+    pub subresources: HashSet<CaseInsensitiveString>,
+}
+
+/// Parse the links of the html resolving them to the base and classifying them by the host of the base. Only the `http` and `https` links are kept.
+pub(crate) fn parse_classified_links(html: &[u8], base: Option<&Url>) -> ClassifiedLinks {
+    let links: std::sync::Mutex<ClassifiedLinks> = Default::default();
+    let host = base.and_then(|base| base.host_str());
+    let base_href: std::sync::OnceLock<Url> = Default::default();
+
+    let resolve = |link: &str| -> Option<Url> {
+        // the protocols not crawled resolve to the base, ex: `mailto:`.
+        if Url::parse(link.trim()).is_ok_and(|url| !matches!(url.scheme(), "http" | "https")) {
+            return None;
+        }
+
+        let url = match base_href.get().or(base) {
+            Some(base) => convert_abs_path(base, link),
+            _ => Url::parse(link.trim()).ok()?,
+        };
+
+        matches!(url.scheme(), "http" | "https").then_some(url)
+    };
+
+    let rewriter_settings = lol_html::Settings {
+        element_content_handlers: vec![
+            lol_html::element!("base[href]", |el| {
+                if let Some(href) = el.get_attribute("href") {
+                    if let Some(parsed_base) = resolve_base_href(&href, base) {
+                        let _ = base_href.set(parsed_base);
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!("a[href], area[href]", |el| {
+                if let Some(url) = el.get_attribute("href").and_then(|href| resolve(&href)) {
+                    let internal = match (host, url.host_str()) {
+                        (Some(host), Some(link_host)) => host.eq_ignore_ascii_case(link_host),
+                        _ => false,
+                    };
+
+                    if let Ok(mut links) = links.lock() {
+                        if internal {
+                            links.internal.insert(url.as_str().into());
+                        } else {
+                            links.external.insert(url.as_str().into());
+                        }
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!(SUBRESOURCE_SRC_ELEMENTS, |el| {
+                if let Some(url) = el.get_attribute("src").and_then(|src| resolve(&src)) {
+                    if let Ok(mut links) = links.lock() {
+                        links.subresources.insert(url.as_str().into());
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!("link[href]", |el| {
+                let subresource = el.get_attribute("rel").is_some_and(|rel| {
+                    rel.split_ascii_whitespace().any(|r| {
+                        ["stylesheet", "icon", "preload", "modulepreload", "manifest"]
+                            .iter()
+                            .any(|v| r.eq_ignore_ascii_case(v))
+                    })
+                });
+
+                if subresource {
+                    if let Some(url) = el.get_attribute("href").and_then(|href| resolve(&href)) {
+                        if let Ok(mut links) = links.lock() {
+                            links.subresources.insert(url.as_str().into());
+                        }
+                    }
+                }
+                Ok(())
+            }),
+        ],
+        adjust_charset_on_meta_tag: true,
+        ..lol_html::send::Settings::new_for_handler_types()
+    };
+
+    let mut rewriter = lol_html::send::HtmlRewriter::new(rewriter_settings, |_c: &[u8]| {});
+
+    if rewriter.write(html).is_ok() {
+        let _ = rewriter.end();
+    } else {
+        drop(rewriter);
+    }
+
+    links.into_inner().unwrap_or_default()
+}
+
+/// The elements not rendered as text.
+const HIDDEN_TEXT_ELEMENTS: &str =
+    "head, title, script, style, noscript, template, svg, iframe, object";
+
+/// The block elements separating the text.
+const BLOCK_TEXT_ELEMENTS: &str = "address, article, aside, blockquote, br, dd, div, dl, dt, fieldset, figcaption, figure, footer, form, h1, h2, h3, h4, h5, h6, header, hr, li, main, nav, ol, p, pre, section, table, td, th, tr, ul";
+
+/// Gzip the html to store the page compressed in memory.
+#[cfg(feature = "compress_html")]
+fn gzip_html(html: &[u8]) -> Option<Vec<u8>> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(
+        Vec::with_capacity(html.len() / 4),
+        flate2::Compression::fast(),
+    );
+
+    encoder.write_all(html).ok()?;
+    encoder.finish().ok()
+}
+
+/// Decompress the html of the page stored compressed.
+#[cfg(feature = "compress_html")]
+fn gunzip_html(compressed: &[u8]) -> Vec<u8> {
+    use std::io::Read;
+
+    let mut html = Vec::new();
+
+    if let Err(e) = flate2::read::GzDecoder::new(compressed).read_to_end(&mut html) {
+        log::warn!("failed to decompress the page html: {e}");
+    }
+
+    html
+}
+
+/// The average words read per minute for the reading time.
+const READING_WORDS_PER_MINUTE: u64 = 238;
+
+/// Parse the visible text of the html removing the scripts, styles, and hidden elements with the whitespace normalized.
+pub(crate) fn parse_text(html: &[u8]) -> String {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    let text: Arc<Mutex<String>> = Default::default();
+    let hidden = Arc::new(AtomicUsize::new(0));
+
+    let push_space = |text: &Mutex<String>| {
+        if let Ok(mut text) = text.lock() {
+            text.push(' ');
+        }
+    };
+
+    let rewriter_settings = lol_html::send::Settings {
+        element_content_handlers: vec![
+            lol_html::element!(HIDDEN_TEXT_ELEMENTS, |el: &mut lol_html::send::Element| {
+                if let Some(handlers) = el.end_tag_handlers() {
+                    let hidden = hidden.clone();
+                    hidden.fetch_add(1, Ordering::Relaxed);
+                    handlers.push(Box::new(move |_end| {
+                        hidden.fetch_sub(1, Ordering::Relaxed);
+                        Ok(())
+                    }));
+                }
+                Ok(())
+            }),
+            lol_html::element!(BLOCK_TEXT_ELEMENTS, |el: &mut lol_html::send::Element| {
+                push_space(&text);
+
+                if let Some(handlers) = el.end_tag_handlers() {
+                    let text = text.clone();
+                    handlers.push(Box::new(move |_end| {
+                        push_space(&text);
+                        Ok(())
+                    }));
+                }
+                Ok(())
+            }),
+        ],
+        document_content_handlers: vec![lol_html::doc_text!(|chunk| {
+            if hidden.load(Ordering::Relaxed) == 0 {
+                if let Ok(mut text) = text.lock() {
+                    text.push_str(chunk.as_str());
+                }
+            }
+            Ok(())
+        })],
+        adjust_charset_on_meta_tag: true,
+        ..lol_html::send::Settings::new_for_handler_types()
+    };
+
+    let mut rewriter = lol_html::send::HtmlRewriter::new(rewriter_settings, |_c: &[u8]| {});
+
+    if rewriter.write(html).is_ok() {
+        let _ = rewriter.end();
+    } else {
+        drop(rewriter);
+    }
+
+    let text = Arc::try_unwrap(text)
+        .ok()
+        .and_then(|text| text.into_inner().ok())
+        .unwrap_or_default();
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse the next page links of the html from the `rel="next"` links and the anchors matching the text patterns.
+pub(crate) fn parse_pagination_links(
+    html: &[u8],
+    base: &Url,
+    config: &PaginationConfig,
+) -> Vec<Url> {
+    let mut links: Vec<Url> = Vec::new();
+
+    if config.rel_next {
+        let mut rel_next = Vec::new();
+
+        let rewriter_settings = lol_html::Settings {
+            element_content_handlers: vec![lol_html::element!("link[rel][href]", |el| {
+                if let (Some(rel), Some(href)) = (el.get_attribute("rel"), el.get_attribute("href"))
+                {
+                    if rel
+                        .split_ascii_whitespace()
+                        .any(|r| r.eq_ignore_ascii_case("next"))
+                    {
+                        rel_next.push(href);
+                    }
+                }
+                Ok(())
+            })],
+            adjust_charset_on_meta_tag: true,
+            ..lol_html::send::Settings::new_for_handler_types()
+        };
+
+        let mut rewriter = lol_html::send::HtmlRewriter::new(rewriter_settings, |_c: &[u8]| {});
+
+        if rewriter.write(html).is_ok() {
+            let _ = rewriter.end();
+        } else {
+            drop(rewriter);
+        }
+
+        links.extend(rel_next.iter().map(|href| convert_abs_path(base, href)));
+    }
+
+    for anchor in parse_anchors(html, Some(base)) {
+        let next = (config.rel_next && anchor.has_rel("next"))
+            || config.matches_text(&anchor.text)
+            || anchor
+                .title
+                .as_deref()
+                .is_some_and(|title| config.matches_text(title));
+
+        if next {
+            if let Ok(link) = Url::parse(&anchor.href) {
+                links.push(link);
+            }
+        }
+    }
+
+    let mut seen = HashSet::with_capacity(links.len());
+    links.retain(|link| seen.insert(link.clone()));
+    links
+}
+
+/// Open Graph metadata extracted from `<meta property="og:*">` and `<meta property="article:*">` tags.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenGraph {
+    /// The Open Graph title (`og:title`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub title: Option<CompactString>,
+    /// The Open Graph description (`og:description`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub description: Option<CompactString>,
+    /// The Open Graph image URL (`og:image`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub image: Option<CompactString>,
+    /// The canonical page URL (`og:url`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub url: Option<CompactString>,
+    /// The content type (`og:type`, e.g., "article", "website").
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub content_type: Option<CompactString>,
+    /// The site name (`og:site_name`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub site_name: Option<CompactString>,
+    /// The locale of the content (`og:locale`, e.g., "en_US").
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub locale: Option<CompactString>,
+    /// The author's name (`article:author` or `og:author`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub author: Option<CompactString>,
+    /// The time the content was first published (`article:published_time`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub published_time: Option<CompactString>,
+    /// The time the content was last modified (`article:modified_time`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub modified_time: Option<CompactString>,
+}
+
+impl OpenGraph {
+    /// Build the Open Graph metadata from the tags as property and content. Returns `None` when no tags are found.
+    pub fn from_tags(tags: &[(CompactString, CompactString)]) -> Option<Self> {
+        let mut og = OpenGraph::default();
+
+        for (property, content) in tags {
+            let field = match property.as_str() {
+                "og:title" => &mut og.title,
+                "og:description" => &mut og.description,
+                "og:image" | "og:image:url" => &mut og.image,
+                "og:url" => &mut og.url,
+                "og:type" => &mut og.content_type,
+                "og:site_name" => &mut og.site_name,
+                "og:locale" => &mut og.locale,
+                "og:author" | "article:author" => &mut og.author,
+                "article:published_time" => &mut og.published_time,
+                "article:modified_time" => &mut og.modified_time,
+                _ => continue,
+            };
+
+            if field.is_none() {
+                *field = Some(content.clone());
+            }
+        }
+
+        if og == OpenGraph::default() {
+            None
+        } else {
+            Some(og)
+        }
+    }
+}
+
+/// Twitter Card metadata extracted from `<meta name="twitter:*">` tags.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwitterCard {
+    /// The card type (`twitter:card`, e.g., "summary_large_image").
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub card: Option<CompactString>,
+    /// The site account (`twitter:site`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub site: Option<CompactString>,
+    /// The author account (`twitter:creator`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub creator: Option<CompactString>,
+    /// The title (`twitter:title`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub title: Option<CompactString>,
+    /// The description (`twitter:description`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub description: Option<CompactString>,
+    /// The image URL (`twitter:image`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub image: Option<CompactString>,
+}
+
+impl TwitterCard {
+    /// Build the Twitter Card metadata from the tags as name and content. Returns `None` when no tags are found.
+    pub fn from_tags(tags: &[(CompactString, CompactString)]) -> Option<Self> {
+        let mut card = TwitterCard::default();
+
+        for (name, content) in tags {
+            let field = match name.as_str() {
+                "twitter:card" => &mut card.card,
+                "twitter:site" => &mut card.site,
+                "twitter:creator" => &mut card.creator,
+                "twitter:title" => &mut card.title,
+                "twitter:description" => &mut card.description,
+                "twitter:image" | "twitter:image:src" => &mut card.image,
+                _ => continue,
+            };
+
+            if field.is_none() {
+                *field = Some(content.clone());
+            }
+        }
+
+        if card == TwitterCard::default() {
+            None
+        } else {
+            Some(card)
+        }
+    }
+}
 
 /// Enumeration of known anti-bot and fraud prevention technologies.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -530,6 +1297,28 @@ pub struct Page {
     pub anti_bot_tech: AntiBotTech,
     /// Page metadata.
     pub metadata: Option<Box<Metadata>>,
+    /// The detected encoding of the page.
+    encoding: Option<&'static auto_encoder::encoding_rs::Encoding>,
+    /// The amount of redirects followed for the page.
+    redirect_count: usize,
+    /// The redirects of the page looped back to a url that was already requested.
+    redirect_loop: bool,
+    /// The entry of the feed that linked to the page.
+    feed_entry: Option<Box<crate::utils::feed::FeedEntry>>,
+    /// The page was served from a cache.
+    from_cache: bool,
+    /// The time from the start of the request to the page built.
+    latency: Option<Duration>,
+    /// The visible text of the page parsed on the first access.
+    text: std::sync::OnceLock<String>,
+    #[cfg(feature = "compress_html")]
+    /// The html is stored gzip compressed.
+    compressed: bool,
+    #[cfg(feature = "compress_html")]
+    /// The html stored compressed decompressed on the first access of the borrowed bytes.
+    decompressed: std::sync::OnceLock<Vec<u8>>,
+    /// Only extract the links inside the elements matching the CSS selector.
+    link_scope: Option<String>,
 }
 
 /// Represent a page visited.
@@ -577,6 +1366,28 @@ pub struct Page {
     pub anti_bot_tech: AntiBotTech,
     /// Page metadata.
     pub metadata: Option<Box<Metadata>>,
+    /// The detected encoding of the page.
+    encoding: Option<&'static auto_encoder::encoding_rs::Encoding>,
+    /// The amount of redirects followed for the page.
+    redirect_count: usize,
+    /// The redirects of the page looped back to a url that was already requested.
+    redirect_loop: bool,
+    /// The entry of the feed that linked to the page.
+    feed_entry: Option<Box<crate::utils::feed::FeedEntry>>,
+    /// The page was served from a cache.
+    from_cache: bool,
+    /// The time from the start of the request to the page built.
+    latency: Option<Duration>,
+    /// The visible text of the page parsed on the first access.
+    text: std::sync::OnceLock<String>,
+    #[cfg(feature = "compress_html")]
+    /// The html is stored gzip compressed.
+    compressed: bool,
+    #[cfg(feature = "compress_html")]
+    /// The html stored compressed decompressed on the first access of the borrowed bytes.
+    decompressed: std::sync::OnceLock<Vec<u8>>,
+    /// Only extract the links inside the elements matching the CSS selector.
+    link_scope: Option<String>,
 }
 
 /// Assign properties from a new page.
@@ -609,6 +1420,12 @@ pub fn page_assign(page: &mut Page, new_page: Page) {
         page.bytes_transferred = new_page.bytes_transferred;
         if new_page.html.is_some() {
             page.html = new_page.html;
+            page.text = Default::default();
+            #[cfg(feature = "compress_html")]
+            {
+                page.compressed = new_page.compressed;
+                page.decompressed = new_page.decompressed;
+            }
         }
     }
 
@@ -645,6 +1462,12 @@ pub fn page_assign(page: &mut Page, new_page: Page) {
     page.should_retry = new_page.should_retry;
     page.signature = new_page.signature;
     page.metadata = new_page.metadata;
+    page.encoding = new_page.encoding;
+    page.redirect_count = new_page.redirect_count;
+    page.redirect_loop = new_page.redirect_loop;
+    page.feed_entry = new_page.feed_entry;
+    page.from_cache = new_page.from_cache;
+    page.latency = new_page.latency;
 }
 
 /// Validate link and push into the map
@@ -658,6 +1481,43 @@ pub(crate) fn validate_link<A: PartialEq + Eq + std::hash::Hash + From<String>>(
     external_domains_caseless: &Box<HashSet<CaseInsensitiveString>>,
     links_pages: &mut Option<HashSet<A>>,
 ) -> Option<Url> {
+    let domains = LinkDomains {
+        base_domain,
+        parent_host,
+        base_input_domain,
+        sub_matcher,
+    };
+
+    validate_link_domain(base, href, &domains, external_domains_caseless, links_pages).ok()
+}
+
+/// The domains of the crawl the links are matched against.
+struct LinkDomains<'a> {
+    /// The domain of the crawl.
+    base_domain: &'a CompactString,
+    /// The host of the parent page.
+    parent_host: &'a CompactString,
+    /// The domain of the url input.
+    base_input_domain: &'a CompactString,
+    /// The subdomain matcher.
+    sub_matcher: &'a CompactString,
+}
+
+/// Validate the link returning the absolute link outside the domain of the crawl as the error.
+fn validate_link_domain<A: PartialEq + Eq + std::hash::Hash + From<String>>(
+    base: &Option<&Url>,
+    href: &str,
+    domains: &LinkDomains,
+    external_domains_caseless: &HashSet<CaseInsensitiveString>,
+    links_pages: &mut Option<HashSet<A>>,
+) -> Result<Url, Option<Url>> {
+    let LinkDomains {
+        base_domain,
+        parent_host,
+        base_input_domain,
+        sub_matcher,
+    } = *domains;
+
     if let Some(b) = base {
         let abs = convert_abs_path(b, href);
 
@@ -700,11 +1560,16 @@ pub(crate) fn validate_link<A: PartialEq + Eq + std::hash::Hash + From<String>>(
                         .contains::<CaseInsensitiveString>(&CASELESS_WILD_CARD);
             }
             if can_process {
-                return Some(abs);
+                return Ok(abs);
             }
+
+            return Err(Some(abs));
+        } else if scheme == "file" && b.scheme() == "file" {
+            // local files link to the sibling files on disk.
+            return Ok(abs);
         }
     }
-    None
+    Err(None)
 }
 
 /// determine a url is relative page
@@ -722,7 +1587,7 @@ pub(crate) fn relative_directory_url(href: &str) -> bool {
     }
 }
 
-/// Validate link and push into the map without extended verify.
+/// Validate link and push into the map without extended verify. The link outside the domain of the crawl is returned.
 pub(crate) fn push_link<A: PartialEq + Eq + std::hash::Hash + From<String>>(
     base: &Option<&Url>,
     href: &str,
@@ -734,23 +1599,32 @@ pub(crate) fn push_link<A: PartialEq + Eq + std::hash::Hash + From<String>>(
     sub_matcher: &CompactString,
     external_domains_caseless: &Box<HashSet<CaseInsensitiveString>>,
     links_pages: &mut Option<HashSet<A>>,
-) {
-    let abs = validate_link(
-        base,
-        href,
+) -> Option<Url> {
+    let domains = LinkDomains {
         base_domain,
         parent_host,
         base_input_domain,
         sub_matcher,
-        external_domains_caseless,
-        links_pages,
-    );
+    };
 
-    if let Some(mut abs) = abs {
-        if abs.scheme() != parent_host_scheme.as_str() {
-            let _ = abs.set_scheme(parent_host_scheme.as_str());
+    let abs = validate_link_domain(base, href, &domains, external_domains_caseless, links_pages);
+
+    match abs {
+        Ok(mut abs) => {
+            if abs.scheme() != parent_host_scheme.as_str() {
+                let _ = abs.set_scheme(parent_host_scheme.as_str());
+            }
+            map.insert(abs.as_str().to_string().into());
+            None
         }
-        map.insert(abs.as_str().to_string().into());
+        Err(off_domain) => off_domain,
+    }
+}
+
+/// Record the link outside the domain of the crawl when the skipped links are tracked.
+fn track_off_domain(skipped_urls: &Option<crate::website::SkippedUrls>, link: Option<Url>) {
+    if let (Some(skipped_urls), Some(link)) = (skipped_urls, link) {
+        skipped_urls.insert(&link.as_str().into(), crate::website::SkipReason::OffDomain);
     }
 }
 
@@ -794,6 +1668,11 @@ pub(crate) fn push_link_verify<A: PartialEq + Eq + std::hash::Hash + From<String
 /// Determine if a url is an asset.
 pub fn is_asset_url(url: &str) -> bool {
     let mut asset = false;
+    // strip the query and fragment so cache busting params like ?v=1.2.3 do not hide the extension.
+    let url = match url.find(['?', '#']) {
+        Some(end) => &url[..end],
+        _ => url,
+    };
     if let Some(position) = url.rfind('.') {
         if url.len() - position >= 3 {
             asset = IGNORE_ASSETS.contains::<CaseInsensitiveString>(&url[position + 1..].into());
@@ -942,6 +1821,184 @@ pub fn validate_empty(content: &Option<Box<Vec<u8>>>, is_success: bool) -> bool
     }
 }
 
+/// The redirect chain looped back to a url that was already requested.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RedirectLoopError;
+
+impl std::fmt::Display for RedirectLoopError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("redirect loop detected")
+    }
+}
+
+impl std::error::Error for RedirectLoopError {}
+
+/// The redirect was refused because the scheme of the url is not allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectSchemeError(pub String);
+
+impl std::fmt::Display for RedirectSchemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "redirect to the disallowed scheme {}", self.0)
+    }
+}
+
+impl std::error::Error for RedirectSchemeError {}
+
+/// Is the request error a redirect loop.
+pub(crate) fn is_redirect_loop(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current_error = Some(error);
+
+    while let Some(err) = current_error {
+        if err.is::<RedirectLoopError>() {
+            return true;
+        }
+        current_error = err.source();
+    }
+
+    false
+}
+
+/// Take the amount of redirects followed for the url.
+pub(crate) fn take_redirect_count(redirect_counts: &Option<RedirectCounts>, url: &str) -> usize {
+    match redirect_counts {
+        Some(redirect_counts) => match redirect_counts.lock() {
+            Ok(mut counts) => match counts.remove(url) {
+                Some(count) => count,
+                _ => Url::parse(url)
+                    .ok()
+                    .and_then(|url| counts.remove(url.as_str()))
+                    .unwrap_or_default(),
+            },
+            _ => 0,
+        },
+        _ => 0,
+    }
+}
+
+/// Send the request of the url with the request settings of the crawl: the transport, the rate limiter, the custom requests, and the connection counting.
+pub(crate) async fn send_request(
+    url: &str,
+    client: &Client,
+    r_settings: &PageLinkBuildSettings,
+) -> Result<crate::client::Response, crate::utils::RequestError> {
+    if let Some(res) = crate::utils::transport::transport_response(&r_settings.transport, url) {
+        return Ok(res);
+    }
+
+    if let Some(rate_limiter) = &r_settings.rate_limiter {
+        rate_limiter.acquire(url).await;
+    }
+
+    let custom = crawl_request(url, r_settings);
+
+    let mut builder = match custom {
+        Some(request) => client.request(request.method.clone(), request.url.as_str()),
+        _ => client.get(url),
+    };
+    if let Some(headers) = &r_settings.navigation_headers {
+        builder = builder.headers(headers.clone());
+    }
+    if let Some(request) = custom {
+        if let Some(headers) = &request.headers {
+            builder = builder.headers(headers.clone());
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+    }
+    let request = builder.send();
+
+    match &r_settings.connection_stats {
+        Some(connection_stats) => connection_stats.scope(url, request).await,
+        _ => request.await,
+    }
+}
+
+/// The custom request of the page url.
+fn crawl_request<'a>(url: &str, r_settings: &'a PageLinkBuildSettings) -> Option<&'a CrawlRequest> {
+    r_settings
+        .requests
+        .as_ref()
+        .and_then(|requests| requests.get(url))
+}
+
+/// The response head and the request of the exchange written to the WARC archive.
+#[cfg(feature = "warc")]
+struct WarcExchange {
+    /// The url of the response.
+    url: String,
+    /// The status of the response.
+    status: StatusCode,
+    /// The HTTP version of the response.
+    version: String,
+    /// The headers of the response.
+    headers: crate::client::header::HeaderMap,
+    /// The custom request of the response. The redirected responses were requested with a `GET`.
+    request: Option<CrawlRequest>,
+}
+
+#[cfg(feature = "warc")]
+impl WarcExchange {
+    /// The exchange of the response when the crawl writes a WARC archive.
+    fn new(
+        url: &str,
+        response: &Result<crate::client::Response, crate::utils::RequestError>,
+        r_settings: &PageLinkBuildSettings,
+    ) -> Option<Self> {
+        match (&r_settings.warc, response) {
+            (Some(_), Ok(res)) => Some(Self {
+                url: res.url().to_string(),
+                status: res.status(),
+                version: format!("{:?}", res.version()),
+                headers: res.headers().clone(),
+                request: crawl_request(url, r_settings)
+                    .filter(|request| Url::parse(&request.url).is_ok_and(|u| &u == res.url()))
+                    .cloned(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Write the exchange with the body of the page response.
+    fn write(self, r_settings: &PageLinkBuildSettings, page_response: &PageResponse) {
+        if let Some(warc) = &r_settings.warc {
+            let body = page_response.content.as_deref().map(|c| c.as_slice());
+
+            if let Err(err) = warc.write_exchange(
+                &self.url,
+                self.status,
+                &self.version,
+                &self.headers,
+                body.unwrap_or_default(),
+                self.request.as_ref(),
+            ) {
+                log::warn!("failed to write the WARC records of {}: {err}", self.url);
+            }
+        }
+    }
+}
+
+/// The page response of a failed request.
+fn request_error_response(url: &str, err: crate::utils::RequestError) -> PageResponse {
+    log::info!("error fetching {}", url);
+
+    let mut page_response = PageResponse::default();
+
+    if let Some(status_code) = err.status() {
+        page_response.status_code = status_code;
+    } else if is_redirect_loop(&err) {
+        page_response.status_code = StatusCode::LOOP_DETECTED;
+        page_response.redirect_loop = true;
+    } else {
+        page_response.status_code = crate::page::get_error_http_status_code(&err);
+    }
+
+    page_response.error_for_status = Some(Err(err));
+
+    page_response
+}
+
 /// Extract a specific type of error from a chain of errors.
 #[cfg(not(feature = "decentralized"))]
 fn extract_specific_error<'a, T: std::error::Error + 'static>(
@@ -1054,7 +2111,7 @@ pub fn build(url: &str, res: PageResponse) -> Page {
 
     let status = res.status_code;
 
-    let should_retry_status = status.is_server_error()
+    let should_retry_status = status.is_server_error() && !res.redirect_loop
         || matches!(
             status,
             StatusCode::TOO_MANY_REQUESTS | StatusCode::FORBIDDEN | StatusCode::REQUEST_TIMEOUT
@@ -1086,8 +2143,18 @@ pub fn build(url: &str, res: PageResponse) -> Page {
         }
     }
 
+    let encoding = res
+        .charset
+        .or_else(|| res.content.as_deref().map(|c| detect_html_encoding(c)));
+
     Page {
         html: res.content,
+        encoding,
+        redirect_count: res.redirect_count,
+        redirect_loop: res.redirect_loop,
+        from_cache: res.from_cache,
+        #[cfg(feature = "time")]
+        latency: res.duration.map(|start| start.elapsed()),
         #[cfg(feature = "headers")]
         headers: res.headers,
         #[cfg(feature = "remote_addr")]
@@ -1138,8 +2205,18 @@ pub fn build(url: &str, res: PageResponse) -> Page {
 /// Instantiate a new page without scraping it (used for testing purposes).
 #[cfg(feature = "decentralized")]
 pub fn build(_: &str, res: PageResponse) -> Page {
+    let encoding = res
+        .charset
+        .or_else(|| res.content.as_deref().map(|c| detect_html_encoding(c)));
+
     Page {
         html: res.content,
+        encoding,
+        redirect_count: res.redirect_count,
+        redirect_loop: res.redirect_loop,
+        from_cache: res.from_cache,
+        #[cfg(feature = "time")]
+        latency: res.duration.map(|start| start.elapsed()),
         #[cfg(feature = "headers")]
         headers: res.headers,
         #[cfg(feature = "remote_addr")]
@@ -1190,8 +2267,127 @@ pub fn build_cookie_header_from_set_cookie(_page: &Page) -> Option<reqwest::head
     None
 }
 
-/// Settings for streaming rewriter
-#[derive(Debug, Default, Clone, Copy)]
+/// The action to take after inspecting the response headers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseAction {
+    /// Continue downloading the body.
+    #[default]
+    Continue,
+    /// Abort the request before the body downloads.
+    Abort,
+}
+
+/// A piece of the page body delivered while the download is in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageChunk {
+    /// A chunk of the body as it arrived from the network.
+    Chunk {
+        /// The url of the page.
+        url: String,
+        /// The raw bytes of the chunk.
+        bytes: bytes::Bytes,
+    },
+    /// The body finished downloading.
+    Complete {
+        /// The url of the page.
+        url: String,
+    },
+}
+
+/// Inspect the response headers before the body downloads.
+pub type OnResponseHeadersCallback =
+    std::sync::Arc<dyn Fn(&reqwest::header::HeaderMap, &Url) -> ResponseAction + Send + Sync>;
+
+/// Transform the html of the page before the links are extracted and the page is stored.
+pub type OnHtmlCallback = std::sync::Arc<dyn Fn(String, &Url) -> String + Send + Sync>;
+
+/// Extract the links from the body of a response that is not html.
+pub type LinkExtractor = std::sync::Arc<dyn Fn(&[u8], &Url) -> Vec<Url> + Send + Sync>;
+
+/// The link extractors with the content type pattern they run on.
+pub type LinkExtractors = std::sync::Arc<Vec<(String, LinkExtractor)>>;
+
+/// The pagination config with the next page links found to crawl first.
+pub type PaginationFrontier = std::sync::Arc<(
+    PaginationConfig,
+    std::sync::Mutex<HashSet<CaseInsensitiveString>>,
+)>;
+
+/// A request to crawl with a custom method, headers, and body, ex: a POST only search endpoint.
+#[derive(Debug, Clone)]
+pub struct CrawlRequest {
+    /// The url of the request.
+    pub url: String,
+    /// The HTTP method of the request.
+    pub method: reqwest::Method,
+    /// The extra headers of the request.
+    pub headers: Option<reqwest::header::HeaderMap>,
+    /// The body of the request.
+    pub body: Option<Vec<u8>>,
+}
+
+impl CrawlRequest {
+    /// A new `GET` request for the url.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.into(),
+            method: reqwest::Method::GET,
+            headers: None,
+            body: None,
+        }
+    }
+
+    /// A new `POST` request for the url with the body.
+    pub fn post(url: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self::new(url)
+            .with_method(reqwest::Method::POST)
+            .with_body(body)
+    }
+
+    /// Set the HTTP method.
+    pub fn with_method(mut self, method: reqwest::Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set the extra headers.
+    pub fn with_headers(mut self, headers: reqwest::header::HeaderMap) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
+    /// Set the body.
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+}
+
+/// The custom requests to crawl by the url of the page, the requests to the same url are keyed with a `#request-N` fragment.
+pub type CrawlRequests = std::sync::Arc<hashbrown::HashMap<String, CrawlRequest>>;
+
+/// Insert the request keyed by the url of its page, the requests to a url already requested get the `#request-N` fragment.
+pub(crate) fn insert_request(
+    requests: &mut hashbrown::HashMap<String, CrawlRequest>,
+    request: CrawlRequest,
+) {
+    let mut key = request.url.clone();
+    let mut n = 0;
+
+    while requests.contains_key(&key) {
+        n += 1;
+        key = format!("{}#request-{n}", request.url);
+    }
+
+    requests.insert(key, request);
+}
+
+/// The redirects followed by the first url of the redirect chain.
+pub type RedirectCounts = std::sync::Arc<std::sync::Mutex<hashbrown::HashMap<String, usize>>>;
+
+/// Settings for streaming rewriter. The settings hold the callbacks and the shared state of the crawl so they are cloned instead of copied, `Debug` is implemented by hand.
+#[derive(Default, Clone)]
 pub struct PageLinkBuildSettings {
     /// If the SSG build is in progress.
     pub ssg_build: bool,
@@ -1203,18 +2399,91 @@ pub struct PageLinkBuildSettings {
     pub subdomains: bool,
     /// De-duplication signature.
     pub normalize: bool,
+    /// Inspect the response headers before the body downloads.
+    pub on_response_headers: Option<OnResponseHeadersCallback>,
+    /// Transform the html before the links are extracted.
+    pub on_html: Option<OnHtmlCallback>,
+    /// Send the body chunks as they arrive.
+    pub chunks: Option<tokio::sync::broadcast::Sender<PageChunk>>,
+    /// Custom link extractors by content type.
+    pub link_extractors: Option<LinkExtractors>,
+    /// Follow the entries of RSS and Atom feeds.
+    pub feed_parsing: bool,
+    /// The feed entries by link attached to the entry pages.
+    pub feed_entries: Option<crate::utils::feed::FeedEntries>,
+    /// The navigation headers of a real browser sent with the page requests.
+    pub navigation_headers: Option<reqwest::header::HeaderMap>,
+    /// Stop downloading the body once the `<head>` ends.
+    pub head_only: bool,
+    /// Follow the `<meta http-equiv="refresh">` redirects.
+    pub follow_meta_refresh: bool,
+    /// Count the requests by host.
+    pub connection_stats: Option<crate::utils::connect::ConnectionStats>,
+    /// Serve the responses without the network.
+    pub transport: Option<std::sync::Arc<dyn crate::utils::transport::Transport>>,
+    /// Sniff the leading bytes of the responses with a missing, generic, or ignored content type.
+    pub mime_sniffing: bool,
+    /// Only follow the links inside the elements matching the CSS selector.
+    pub link_scope: Option<String>,
+    /// Follow the next page links first.
+    pub pagination: Option<PaginationFrontier>,
+    /// The redirects followed by the redirect policy.
+    pub redirect_counts: Option<RedirectCounts>,
+    /// Limit the requests per second by host.
+    pub rate_limiter: Option<crate::utils::rate_limiter::RateLimiter>,
+    /// The custom method, headers, and body of the requests by the url of the page.
+    pub requests: Option<CrawlRequests>,
+    /// Record the time the pages were fetched successfully for the recrawls.
+    pub timestamp_store: Option<std::sync::Arc<dyn crate::utils::url_store::UrlStore>>,
+    /// Record the links outside the domain of the crawl.
+    pub skipped_urls: Option<crate::website::SkippedUrls>,
+    #[cfg(feature = "warc")]
+    /// Write the requests and responses to a WARC archive.
+    pub warc: Option<std::sync::Arc<crate::utils::warc::WarcWriter>>,
 }
 
-impl PageLinkBuildSettings {
-    /// New build link settings.
-    pub(crate) fn new(ssg_build: bool, full_resources: bool) -> Self {
-        Self {
-            ssg_build,
-            full_resources,
-            ..Default::default()
+impl std::fmt::Debug for PageLinkBuildSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("PageLinkBuildSettings");
+
+        ds.field("ssg_build", &self.ssg_build)
+            .field("full_resources", &self.full_resources)
+            .field("tld", &self.tld)
+            .field("subdomains", &self.subdomains)
+            .field("normalize", &self.normalize)
+            .field("on_response_headers", &self.on_response_headers.is_some())
+            .field("on_html", &self.on_html.is_some())
+            .field("chunks", &self.chunks.is_some())
+            .field(
+                "link_extractors",
+                &self.link_extractors.as_ref().map(|l| l.len()),
+            )
+            .field("feed_parsing", &self.feed_parsing)
+            .field("feed_entries", &self.feed_entries.is_some())
+            .field("navigation_headers", &self.navigation_headers.is_some())
+            .field("head_only", &self.head_only)
+            .field("follow_meta_refresh", &self.follow_meta_refresh)
+            .field("connection_stats", &self.connection_stats.is_some())
+            .field("transport", &self.transport.is_some())
+            .field("mime_sniffing", &self.mime_sniffing)
+            .field("link_scope", &self.link_scope)
+            .field("pagination", &self.pagination.as_ref().map(|p| &p.0))
+            .field("redirect_counts", &self.redirect_counts.is_some())
+            .field("rate_limiter", &self.rate_limiter.is_some())
+            .field("requests", &self.requests.as_ref().map(|r| r.len()))
+            .field("timestamp_store", &self.timestamp_store.is_some())
+            .field("skipped_urls", &self.skipped_urls.is_some());
+
+        #[cfg(feature = "warc")]
+        {
+            ds.field("warc", &self.warc.is_some());
         }
+
+        ds.finish()
     }
+}
 
+impl PageLinkBuildSettings {
     /// New build full link settings.
     pub(crate) fn new_full(
         ssg_build: bool,
@@ -1229,6 +2498,7 @@ impl PageLinkBuildSettings {
             subdomains,
             tld,
             normalize,
+            ..Default::default()
         }
     }
 }
@@ -1237,6 +2507,13 @@ impl PageLinkBuildSettings {
 pub(crate) fn get_charset_from_content_type(
     headers: &reqwest::header::HeaderMap,
 ) -> Option<AsciiCompatibleEncoding> {
+    get_encoding_from_content_type(headers).and_then(AsciiCompatibleEncoding::new)
+}
+
+/// Get the encoding of the charset in the content type of the response.
+pub(crate) fn get_encoding_from_content_type(
+    headers: &reqwest::header::HeaderMap,
+) -> Option<&'static auto_encoder::encoding_rs::Encoding> {
     use auto_encoder::encoding_rs;
 
     if let Some(content_type) = headers.get(reqwest::header::CONTENT_TYPE) {
@@ -1245,10 +2522,9 @@ pub(crate) fn get_charset_from_content_type(
             for part in parts {
                 let part = part.trim().to_lowercase();
                 if let Some(stripped) = part.strip_prefix("charset=") {
+                    let stripped = stripped.trim_matches(['"', '\'']);
                     if let Some(encoding) = encoding_rs::Encoding::for_label(stripped.as_bytes()) {
-                        if let Some(ascii_encoding) = AsciiCompatibleEncoding::new(encoding) {
-                            return Some(ascii_encoding);
-                        }
+                        return Some(encoding);
                     }
                 }
             }
@@ -1258,6 +2534,61 @@ pub(crate) fn get_charset_from_content_type(
     None
 }
 
+/// Get the encoding declared in a `<meta charset>` or `<meta http-equiv="Content-Type">` tag of the first 1024 bytes.
+fn get_encoding_from_meta(html: &[u8]) -> Option<&'static auto_encoder::encoding_rs::Encoding> {
+    use auto_encoder::encoding_rs;
+
+    let head = String::from_utf8_lossy(&html[..html.len().min(1024)]).to_ascii_lowercase();
+
+    for tag in head.split("<meta").skip(1) {
+        let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+
+        if let Some(start) = tag.find("charset=") {
+            let label = tag[start + 8..].trim_start_matches(['"', '\'', ' ']);
+            let end = label
+                .find(|c: char| matches!(c, '"' | '\'' | ';' | '/') || c.is_whitespace())
+                .unwrap_or(label.len());
+
+            if let Some(encoding) = encoding_rs::Encoding::for_label(label[..end].as_bytes()) {
+                // the utf-16 declarations in ascii compatible bytes are utf-8.
+                return Some(
+                    if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+                        encoding_rs::UTF_8
+                    } else {
+                        encoding
+                    },
+                );
+            }
+        }
+    }
+
+    None
+}
+
+/// The amount of leading bytes checked for valid UTF-8 when detecting the encoding.
+const ENCODING_SNIFF_LENGTH: usize = 4096;
+
+/// Detect the encoding of the html without a charset in the content type from the byte order mark, the `<meta charset>` tag, or the leading bytes.
+/// The html that is not valid UTF-8 falls back to `windows-1252` like the browsers.
+pub fn detect_html_encoding(html: &[u8]) -> &'static auto_encoder::encoding_rs::Encoding {
+    use auto_encoder::encoding_rs;
+
+    if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(html) {
+        return encoding;
+    }
+
+    if let Some(encoding) = get_encoding_from_meta(html) {
+        return encoding;
+    }
+
+    match std::str::from_utf8(&html[..html.len().min(ENCODING_SNIFF_LENGTH)]) {
+        Ok(_) => encoding_rs::UTF_8,
+        // the body or the sniffed bytes may be cut off in the middle of a character.
+        Err(err) if err.error_len().is_none() => encoding_rs::UTF_8,
+        _ => encoding_rs::WINDOWS_1252,
+    }
+}
+
 #[cfg(feature = "chrome")]
 /// Set the metadata found on the page.
 
@@ -1343,6 +2674,54 @@ impl Page {
         build(url, page_resource)
     }
 
+    /// Instantiate a new page with the request settings of the crawl without the streaming rewriter. The response headers hook runs before the body downloads.
+    #[cfg(any(test, feature = "smart", feature = "sitemap"))]
+    pub(crate) async fn new_page_settings(
+        url: &str,
+        client: &Client,
+        r_settings: &PageLinkBuildSettings,
+    ) -> Self {
+        let duration = if cfg!(feature = "time") {
+            Some(tokio::time::Instant::now())
+        } else {
+            None
+        };
+
+        let response = send_request(url, client, r_settings).await;
+
+        #[cfg(feature = "warc")]
+        let warc_exchange = WarcExchange::new(url, &response, r_settings);
+
+        let mut page_response = match response {
+            Ok(res)
+                if crate::utils::abort_on_response_headers(
+                    &res,
+                    &r_settings.on_response_headers,
+                ) =>
+            {
+                crate::utils::handle_response_headers_only(res, url)
+            }
+            Ok(res) => crate::utils::handle_response_bytes(res, url, false).await,
+            Err(err) => request_error_response(url, err),
+        };
+
+        page_response.redirect_count = take_redirect_count(&r_settings.redirect_counts, url);
+        crate::utils::set_page_response_duration(&mut page_response, duration);
+
+        #[cfg(feature = "warc")]
+        if let Some(warc_exchange) = warc_exchange {
+            warc_exchange.write(r_settings, &page_response);
+        }
+
+        if let Some(store) = &r_settings.timestamp_store {
+            crate::utils::recrawl::record_fetch(store.as_ref(), url, page_response.status_code);
+        }
+
+        let mut page = build(url, page_response);
+        page.feed_entry = crate::utils::feed::take_feed_entry(&r_settings.feed_entries, url);
+        page
+    }
+
     /// New page with rewriter
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn new_page_streaming<
@@ -1362,13 +2741,14 @@ impl Page {
     ) -> Self {
         use crate::utils::{
             handle_response_bytes, handle_response_bytes_writer, modify_selectors,
-            AllowedDomainTypes,
+            AllowedDomainTypes, ResponseWriterOptions,
         };
 
         let mut metadata: Option<Box<Metadata>> = None;
         let mut meta_title: Option<_> = None;
         let mut meta_description: Option<_> = None;
         let mut meta_og_image: Option<_> = None;
+        let meta_refresh: std::sync::OnceLock<String> = Default::default();
 
         let duration = if cfg!(feature = "time") {
             Some(tokio::time::Instant::now())
@@ -1376,10 +2756,34 @@ impl Page {
             None
         };
 
-        let mut page_response: PageResponse = match client.get(url).send().await {
+        let response = send_request(url, client, r_settings).await;
+
+        #[cfg(feature = "warc")]
+        let warc_exchange = WarcExchange::new(url, &response, r_settings);
+
+        let link_extractor = match &response {
+            Ok(res) if r_settings.link_extractors.is_some() => {
+                crate::utils::find_link_extractor(res, &r_settings.link_extractors)
+            }
+            _ => None,
+        };
+        let feed = r_settings.feed_parsing
+            && link_extractor.is_none()
+            && matches!(&response, Ok(res) if crate::utils::feed::is_feed_response(res));
+
+        let mut page_response: PageResponse = match response {
+            Ok(res)
+                if crate::utils::abort_on_response_headers(
+                    &res,
+                    &r_settings.on_response_headers,
+                ) =>
+            {
+                crate::utils::handle_response_headers_only(res, url)
+            }
             Ok(res)
                 if crate::utils::valid_parsing_status(&res)
-                    && !crate::utils::block_streaming(&res, only_html) =>
+                    && (r_settings.mime_sniffing
+                        || !crate::utils::block_streaming(&res, only_html)) =>
             {
                 let cell = if r_settings.ssg_build {
                     Some(tokio::sync::OnceCell::new())
@@ -1430,45 +2834,57 @@ impl Page {
                 let xml_file = target_url.ends_with(".xml");
 
                 let base_links_settings = if r_settings.full_resources {
-                    lol_html::element!("a[href],script[src],link[href]", |el| {
-                        let tag_name = el.tag_name();
-                        let attribute = if tag_name == "script" { "src" } else { "href" };
+                    lol_html::element!(
+                        scoped_css_selector(
+                            "a[href],script[src],link[href]",
+                            r_settings.link_scope.as_deref()
+                        ),
+                        |el| {
+                            let tag_name = el.tag_name();
+                            let attribute = if tag_name == "script" { "src" } else { "href" };
 
-                        if let Some(href) = el.get_attribute(attribute) {
-                            let base = if relative_directory_url(&href) || base.is_none() {
-                                original_page.as_ref()
-                            } else {
-                                base.as_deref()
-                            };
-                            let base = if base_input_url.initialized() {
-                                base_input_url.get()
-                            } else {
-                                base
-                            };
+                            if let Some(href) = el.get_attribute(attribute) {
+                                let base = if relative_directory_url(&href) || base.is_none() {
+                                    original_page.as_ref()
+                                } else {
+                                    base.as_deref()
+                                };
+                                let base = if base_input_url.initialized() {
+                                    base_input_url.get()
+                                } else {
+                                    base
+                                };
 
-                            push_link(
-                                &base,
-                                &href,
-                                map,
-                                &selectors.0,
-                                parent_host,
-                                parent_host_scheme,
-                                base_input_domain,
-                                sub_matcher,
-                                &external_domains_caseless,
-                                links_pages,
-                            );
-                        }
+                                track_off_domain(
+                                    &r_settings.skipped_urls,
+                                    push_link(
+                                        &base,
+                                        &href,
+                                        map,
+                                        &selectors.0,
+                                        parent_host,
+                                        parent_host_scheme,
+                                        base_input_domain,
+                                        sub_matcher,
+                                        external_domains_caseless,
+                                        links_pages,
+                                    ),
+                                );
+                            }
 
-                        Ok(())
-                    })
+                            Ok(())
+                        }
+                    )
                 } else {
                     lol_html::element!(
-                        if xml_file {
-                            BASE_CSS_SELECTORS_WITH_XML
-                        } else {
-                            BASE_CSS_SELECTORS
-                        },
+                        scoped_css_selector(
+                            if xml_file {
+                                BASE_CSS_SELECTORS_WITH_XML
+                            } else {
+                                BASE_CSS_SELECTORS
+                            },
+                            r_settings.link_scope.as_deref()
+                        ),
                         |el| {
                             if let Some(href) = el.get_attribute("href") {
                                 let base = if relative_directory_url(&href) || base.is_none() {
@@ -1481,17 +2897,20 @@ impl Page {
                                 } else {
                                     base
                                 };
-                                push_link(
-                                    &base,
-                                    &href,
-                                    map,
-                                    &selectors.0,
-                                    parent_host,
-                                    parent_host_scheme,
-                                    base_input_domain,
-                                    sub_matcher,
-                                    &external_domains_caseless,
-                                    links_pages,
+                                track_off_domain(
+                                    &r_settings.skipped_urls,
+                                    push_link(
+                                        &base,
+                                        &href,
+                                        map,
+                                        &selectors.0,
+                                        parent_host,
+                                        parent_host_scheme,
+                                        base_input_domain,
+                                        sub_matcher,
+                                        external_domains_caseless,
+                                        links_pages,
+                                    ),
                                 );
                             }
                             Ok(())
@@ -1504,7 +2923,8 @@ impl Page {
 
                 element_content_handlers.push(lol_html::element!("base", |el| {
                     if let Some(href) = el.get_attribute("href") {
-                        if let Ok(parsed_base) = Url::parse(&href) {
+                        if let Some(parsed_base) = resolve_base_href(&href, original_page.as_ref())
+                        {
                             let _ = base_input_url.set(parsed_base);
                         }
                     }
@@ -1519,6 +2939,18 @@ impl Page {
                     &mut meta_og_image,
                 ));
 
+                if r_settings.follow_meta_refresh {
+                    element_content_handlers.push(lol_html::element!(
+                        META_REFRESH_SELECTOR,
+                        |el| {
+                            if let Some(content) = el.get_attribute("content") {
+                                let _ = meta_refresh.set(content);
+                            }
+                            Ok(())
+                        }
+                    ));
+                }
+
                 if r_settings.ssg_build {
                     element_content_handlers.push(lol_html::element!("script", |el| {
                         if let Some(build_path) = el.get_attribute("src") {
@@ -1534,11 +2966,62 @@ impl Page {
                     }));
                 }
 
-                let settings = lol_html::send::Settings {
-                    element_content_handlers,
-                    adjust_charset_on_meta_tag,
-                    encoding,
-                    ..lol_html::send::Settings::new_for_handler_types()
+                let head_ended = r_settings
+                    .head_only
+                    .then(|| std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)));
+                let mut head_handlers = Vec::with_capacity(2);
+
+                if let Some(head_ended) = &head_ended {
+                    let head_end = head_ended.clone();
+                    let body_start = head_ended.clone();
+
+                    head_handlers.push(lol_html::element!(
+                        "head",
+                        move |el: &mut lol_html::send::Element| {
+                            if let Some(handlers) = el.end_tag_handlers() {
+                                let head_ended = head_end.clone();
+                                handlers.push(Box::new(move |_end| {
+                                    head_ended.store(true, std::sync::atomic::Ordering::Relaxed);
+                                    Ok(())
+                                }));
+                            }
+                            Ok(())
+                        }
+                    ));
+                    // the body can start without the head closed.
+                    head_handlers.push(lol_html::element!(
+                        "body",
+                        move |_el: &mut lol_html::send::Element| {
+                            body_start.store(true, std::sync::atomic::Ordering::Relaxed);
+                            Ok(())
+                        }
+                    ));
+                }
+
+                // the links are extracted after the download when the html is transformed.
+                let (settings, transform_settings) = if r_settings.on_html.is_some() {
+                    (
+                        lol_html::send::Settings {
+                            element_content_handlers: head_handlers,
+                            ..lol_html::send::Settings::new_for_handler_types()
+                        },
+                        Some(lol_html::send::Settings {
+                            element_content_handlers,
+                            ..lol_html::send::Settings::new_for_handler_types()
+                        }),
+                    )
+                } else {
+                    element_content_handlers.extend(head_handlers);
+
+                    (
+                        lol_html::send::Settings {
+                            element_content_handlers,
+                            adjust_charset_on_meta_tag,
+                            encoding,
+                            ..lol_html::send::Settings::new_for_handler_types()
+                        },
+                        None,
+                    )
                 };
 
                 let mut rewriter = lol_html::send::HtmlRewriter::new(settings, |_c: &[u8]| {});
@@ -1556,6 +3039,11 @@ impl Page {
                     only_html,
                     &mut rewriter,
                     &mut collected_bytes,
+                    ResponseWriterOptions {
+                        chunks: r_settings.chunks.as_ref(),
+                        head_ended: head_ended.as_deref(),
+                        mime_sniffing: r_settings.mime_sniffing,
+                    },
                 )
                 .await;
 
@@ -1565,6 +3053,26 @@ impl Page {
                     let _ = rewriter.end();
                 }
 
+                if let (Some(on_html), Some(settings)) = (&r_settings.on_html, transform_settings) {
+                    let target_url = response.0.final_url.as_deref().unwrap_or(url);
+
+                    if let Ok(target_url) = Url::parse(target_url) {
+                        let html = match response.0.charset {
+                            Some(charset) => charset.decode(&collected_bytes).0.into_owned(),
+                            _ => auto_encode_bytes(&collected_bytes),
+                        };
+
+                        collected_bytes = on_html(html, &target_url).into_bytes();
+                        response.0.charset = Some(auto_encoder::encoding_rs::UTF_8);
+                    }
+
+                    let mut rewriter = lol_html::send::HtmlRewriter::new(settings, |_c: &[u8]| {});
+
+                    if rewriter.write(&collected_bytes).is_ok() {
+                        let _ = rewriter.end();
+                    }
+                }
+
                 if r_settings.normalize {
                     response.0.signature = Some(hash_html(&collected_bytes).await);
                 }
@@ -1636,22 +3144,125 @@ impl Page {
                 response.0
             }
             Ok(res) => handle_response_bytes(res, url, only_html).await,
-            Err(err) => {
-                log::info!("error fetching {}", url);
+            Err(err) => request_error_response(url, err),
+        };
+
+        page_response.redirect_count = take_redirect_count(&r_settings.redirect_counts, url);
+
+        if let (Some(link_extractor), Some(content)) = (link_extractor, &page_response.content) {
+            let target_url = page_response.final_url.as_deref().unwrap_or(url);
+
+            if let Ok(base) = Url::parse(target_url) {
+                for link in link_extractor(content.as_slice(), &base) {
+                    track_off_domain(
+                        &r_settings.skipped_urls,
+                        push_link(
+                            &Some(&base),
+                            link.as_str(),
+                            map,
+                            &selectors.0,
+                            &selectors.1[0],
+                            &selectors.1[1],
+                            &selectors.2,
+                            &selectors.0,
+                            external_domains_caseless,
+                            links_pages,
+                        ),
+                    );
+                }
+            }
+        }
 
-                let mut page_response = PageResponse::default();
+        if let (true, Some(content)) = (feed, &page_response.content) {
+            let target_url = page_response.final_url.as_deref().unwrap_or(url);
+
+            if let Ok(base) = Url::parse(target_url) {
+                for link in crate::utils::feed::feed_entry_links(
+                    content.as_slice(),
+                    &base,
+                    &r_settings.feed_entries,
+                ) {
+                    track_off_domain(
+                        &r_settings.skipped_urls,
+                        push_link(
+                            &Some(&base),
+                            link.as_str(),
+                            map,
+                            &selectors.0,
+                            &selectors.1[0],
+                            &selectors.1[1],
+                            &selectors.2,
+                            &selectors.0,
+                            external_domains_caseless,
+                            links_pages,
+                        ),
+                    );
+                }
+            }
+        }
 
-                if let Some(status_code) = err.status() {
-                    page_response.status_code = status_code;
-                } else {
-                    page_response.status_code = crate::page::get_error_http_status_code(&err);
+        if let Some((_, link)) = meta_refresh
+            .get()
+            .and_then(|refresh| parse_meta_refresh_content(refresh))
+        {
+            let target_url = page_response.final_url.as_deref().unwrap_or(url);
+
+            if let Ok(base) = Url::parse(target_url) {
+                let link = convert_abs_path(&base, link);
+
+                track_off_domain(
+                    &r_settings.skipped_urls,
+                    push_link(
+                        &Some(&base),
+                        link.as_str(),
+                        map,
+                        &selectors.0,
+                        &selectors.1[0],
+                        &selectors.1[1],
+                        &selectors.2,
+                        &selectors.0,
+                        external_domains_caseless,
+                        links_pages,
+                    ),
+                );
+            }
+        }
+
+        if let (Some(pagination), Some(content)) = (&r_settings.pagination, &page_response.content)
+        {
+            let target_url = page_response.final_url.as_deref().unwrap_or(url);
+
+            if let Ok(base) = Url::parse(target_url) {
+                let mut next_pages: HashSet<CaseInsensitiveString> = HashSet::new();
+
+                for link in parse_pagination_links(content.as_slice(), &base, &pagination.0) {
+                    push_link(
+                        &Some(&base),
+                        link.as_str(),
+                        &mut next_pages,
+                        &selectors.0,
+                        &selectors.1[0],
+                        &selectors.1[1],
+                        &selectors.2,
+                        &selectors.0,
+                        external_domains_caseless,
+                        &mut None,
+                    );
                 }
 
-                page_response.error_for_status = Some(Err(err));
+                if !next_pages.is_empty() {
+                    map.extend(
+                        next_pages
+                            .iter()
+                            .map(|link| A::from(link.inner().to_string())),
+                    );
 
-                page_response
+                    if let Ok(mut frontier) = pagination.1.lock() {
+                        frontier.extend(next_pages);
+                    }
+                }
             }
-        };
+        }
 
         let valid_meta = meta_title.is_some()
             || meta_description.is_some()
@@ -1677,7 +3288,18 @@ impl Page {
 
         crate::utils::set_page_response_duration(&mut page_response, duration);
 
-        build(url, page_response)
+        #[cfg(feature = "warc")]
+        if let Some(warc_exchange) = warc_exchange {
+            warc_exchange.write(r_settings, &page_response);
+        }
+
+        if let Some(store) = &r_settings.timestamp_store {
+            crate::utils::recrawl::record_fetch(store.as_ref(), url, page_response.status_code);
+        }
+
+        let mut page = build(url, page_response);
+        page.feed_entry = crate::utils::feed::take_feed_entry(&r_settings.feed_entries, url);
+        page
     }
 
     /// Instantiate a new page and gather the html repro of standard fetch_page_html only gathering resources to crawl.
@@ -1960,9 +3582,16 @@ impl Page {
     /// Page request is empty. On chrome an empty page has bare html markup.
     #[inline]
     pub fn is_empty(&self) -> bool {
+        #[cfg(feature = "compress_html")]
+        if self.compressed {
+            // empty pages are never compressed.
+            return false;
+        }
+
         match self.html.as_deref() {
             None => true,
-            Some(html) => {
+            Some(_) => {
+                let html = self.get_html_bytes();
                 let html = html.trim_ascii();
                 html.is_empty() || html.eq(*EMPTY_HTML) || html.eq(*EMPTY_HTML_BASIC)
             }
@@ -2028,9 +3657,51 @@ impl Page {
         self.external_domains_caseless = external_domains_caseless;
     }
 
+    /// Only extract the links inside the elements matching the CSS selector, ex: `main`.
+    pub fn set_link_scope(&mut self, link_scope: Option<String>) {
+        self.link_scope = link_scope;
+    }
+
+    /// Drop the html of the page when the response headers callback aborts. Used by the chrome crawls where the browser downloads the body before the headers can be inspected.
+    #[cfg(all(
+        feature = "chrome",
+        not(feature = "decentralized"),
+        feature = "headers"
+    ))]
+    pub(crate) fn apply_response_headers_hook(
+        &mut self,
+        on_response_headers: &Option<OnResponseHeadersCallback>,
+    ) {
+        if let (Some(cb), Some(headers)) = (on_response_headers, &self.headers) {
+            if let Ok(url) = Url::parse(self.get_url_final()) {
+                if cb(headers, &url) == ResponseAction::Abort {
+                    self.set_html_bytes(None);
+                }
+            }
+        }
+    }
+
+    /// Drop the html of the page when the response headers callback aborts. This does nothing without the `headers` flag enabled.
+    #[cfg(all(
+        feature = "chrome",
+        not(feature = "decentralized"),
+        not(feature = "headers")
+    ))]
+    pub(crate) fn apply_response_headers_hook(
+        &mut self,
+        _on_response_headers: &Option<OnResponseHeadersCallback>,
+    ) {
+    }
+
     /// Set the html directly of the page
     pub fn set_html_bytes(&mut self, html: Option<Vec<u8>>) {
         self.html = html.map(Box::new);
+        self.text = Default::default();
+        #[cfg(feature = "compress_html")]
+        {
+            self.compressed = false;
+            self.decompressed = Default::default();
+        }
     }
 
     /// Set the url directly of the page. Useful for transforming the content and rewriting the url.
@@ -2092,42 +3763,249 @@ impl Page {
         &None
     }
 
-    /// Take the parsed url.
-    #[cfg(not(feature = "decentralized"))]
-    pub fn take_url(&mut self) -> Option<Url> {
-        self.base.take()
+    /// Take the parsed url.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn take_url(&mut self) -> Option<Url> {
+        self.base.take()
+    }
+
+    /// Take the parsed url.
+    #[cfg(feature = "decentralized")]
+    pub fn take_url(&mut self) -> Option<Url> {
+        None
+    }
+
+    #[cfg(feature = "decentralized")]
+    /// URL getter for page.
+    pub fn get_url(&self) -> &str {
+        ""
+    }
+
+    /// Html getter for bytes on the page. The html stored compressed is decompressed once and kept on the page, use `get_html_bytes` to read the html without keeping a copy.
+    pub fn get_bytes(&self) -> Option<&Vec<u8>> {
+        #[cfg(feature = "compress_html")]
+        if self.compressed {
+            return self
+                .html
+                .as_deref()
+                .map(|html| self.decompressed.get_or_init(|| gunzip_html(html)));
+        }
+
+        self.html.as_deref()
+    }
+
+    /// The length of the html of the page without decompressing the html stored compressed.
+    pub fn html_len(&self) -> usize {
+        #[cfg(feature = "compress_html")]
+        if self.compressed {
+            // the gzip trailer ends with the size of the input.
+            let html = self.html.as_deref().map(Vec::as_slice).unwrap_or_default();
+
+            return match html.len().checked_sub(4).and_then(|i| html.get(i..)) {
+                Some(size) => u32::from_le_bytes([size[0], size[1], size[2], size[3]]) as usize,
+                _ => 0,
+            };
+        }
+
+        self.get_html_bytes_u8().len()
+    }
+
+    /// Html getter for bytes on the page as string. The html is decoded to UTF-8 from the detected encoding of the page.
+    pub fn get_html(&self) -> String {
+        if self.html.is_none() {
+            return Default::default();
+        }
+
+        let html = self.get_html_bytes();
+
+        match self.encoding {
+            Some(encoding) if encoding != auto_encoder::encoding_rs::UTF_8 => {
+                encoding.decode(&html).0.into_owned()
+            }
+            _ => auto_encoder::auto_encode_bytes(&html),
+        }
+    }
+
+    /// Html getter for the bytes on the page. The html stored compressed is decompressed on every access without keeping a copy on the page.
+    pub fn get_html_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        #[cfg(feature = "compress_html")]
+        if self.compressed {
+            return match (self.decompressed.get(), self.html.as_deref()) {
+                (Some(html), _) => std::borrow::Cow::Borrowed(html),
+                (_, Some(html)) => std::borrow::Cow::Owned(gunzip_html(html)),
+                _ => Default::default(),
+            };
+        }
+
+        std::borrow::Cow::Borrowed(self.get_html_bytes_u8())
+    }
+
+    /// Compress the html of the page in memory. The html is decompressed on access with `get_html` and `get_html_bytes`, use `get_compressed_bytes` to read the compressed bytes.
+    #[cfg(feature = "compress_html")]
+    pub fn compress_html(&mut self) {
+        if self.compressed || self.is_empty() {
+            return;
+        }
+
+        if let Some(html) = self.html.as_deref() {
+            if let Some(compressed) = gzip_html(html) {
+                self.html = Some(Box::new(compressed));
+                self.compressed = true;
+            }
+        }
+    }
+
+    /// Restore the html of the page compressed with `compress_html`.
+    #[cfg(feature = "compress_html")]
+    pub fn decompress_html(&mut self) {
+        if self.compressed {
+            let html = gunzip_html(self.html.as_deref().map(Vec::as_slice).unwrap_or_default());
+            self.html = Some(Box::new(html));
+            self.compressed = false;
+            self.decompressed = Default::default();
+        }
+    }
+
+    /// The html of the page is stored compressed.
+    #[cfg(feature = "compress_html")]
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// The gzip compressed bytes of the html stored compressed with `compress_html`.
+    #[cfg(feature = "compress_html")]
+    pub fn get_compressed_bytes(&self) -> Option<&Vec<u8>> {
+        if self.compressed {
+            self.html.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// The detected encoding of the page from the charset of the content type, the `<meta charset>` tag, or the bytes, ex: `Shift_JIS`.
+    pub fn encoding(&self) -> Option<&'static str> {
+        self.encoding.map(|encoding| encoding.name())
+    }
+
+    /// The time from the start of the request to the page built. This is always `None` without the `time` flag enabled.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    /// The amount of redirects followed for the page with the HTTP client.
+    pub fn redirect_count(&self) -> usize {
+        self.redirect_count
+    }
+
+    /// The page was served from the http cache, the chrome disk cache, or the stored chrome cache instead of the network.
+    pub fn from_cache(&self) -> bool {
+        self.from_cache
+    }
+
+    /// The redirects of the page looped back to a url that was already requested. The page is also marked with the `508 Loop Detected` status, a `508` sent by the server is not a redirect loop.
+    pub fn is_redirect_loop(&self) -> bool {
+        self.redirect_loop
+    }
+
+    /// Html getter for page to u8. The html stored compressed is decompressed once and kept on the page.
+    pub fn get_html_bytes_u8(&self) -> &[u8] {
+        match self.get_bytes() {
+            Some(html) => html,
+            _ => Default::default(),
+        }
+    }
+
+    /// The title, description, canonical, Open Graph, Twitter Card, and icon tags in the `<head>` of the page.
+    pub fn head_metadata(&self) -> HeadMetadata {
+        parse_head_metadata(&self.get_html_bytes())
+    }
+
+    /// The `<a href>` links of the page in order with the anchor text, `rel`, and `title`.
+    pub fn anchors(&self) -> Vec<Anchor> {
+        let base = Url::parse(self.get_url_final()).ok();
+
+        parse_anchors(&self.get_html_bytes(), base.as_ref())
+    }
+
+    /// The delay in seconds and the url of the `<meta http-equiv="refresh">` redirect of the page. The refresh tags without a url are ignored.
+    pub fn meta_refresh(&self) -> Option<(u32, Url)> {
+        let base = Url::parse(self.get_url_final()).ok()?;
+
+        parse_meta_refresh(&self.get_html_bytes(), &base)
+    }
+
+    /// The html of the page is an empty shell that needs javascript to render the content. See [`needs_rendering`].
+    pub fn needs_rendering(&self) -> bool {
+        needs_rendering(&self.get_html())
+    }
+
+    /// The links of the page classified as internal on the same host, external, and the subresources loaded by the page, ex: images, scripts, and stylesheets.
+    pub fn classified_links(&self) -> ClassifiedLinks {
+        let base = Url::parse(self.get_url_final()).ok();
+
+        parse_classified_links(&self.get_html_bytes(), base.as_ref())
+    }
+
+    /// The visible text of the page with the scripts and styles removed and the whitespace normalized. The text is parsed once and cached.
+    pub fn text(&self) -> String {
+        self.text_cached().to_string()
+    }
+
+    /// The cached visible text of the page.
+    fn text_cached(&self) -> &str {
+        self.text.get_or_init(|| parse_text(&self.get_html_bytes()))
+    }
+
+    /// The amount of words of the visible text of the page.
+    pub fn word_count(&self) -> usize {
+        self.text_cached().split_whitespace().count()
+    }
+
+    /// The estimated time to read the visible text of the page at 238 words per minute.
+    pub fn reading_time(&self) -> Duration {
+        Duration::from_secs(self.word_count() as u64 * 60 / READING_WORDS_PER_MINUTE)
     }
 
-    /// Take the parsed url.
-    #[cfg(feature = "decentralized")]
-    pub fn take_url(&mut self) -> Option<Url> {
-        None
+    /// The Open Graph metadata of the page.
+    pub fn open_graph(&self) -> Option<OpenGraph> {
+        OpenGraph::from_tags(&self.head_metadata().open_graph)
     }
 
-    #[cfg(feature = "decentralized")]
-    /// URL getter for page.
-    pub fn get_url(&self) -> &str {
-        ""
+    /// The Twitter Card metadata of the page.
+    pub fn twitter_card(&self) -> Option<TwitterCard> {
+        TwitterCard::from_tags(&self.head_metadata().twitter)
     }
 
-    /// Html getter for bytes on the page.
-    pub fn get_bytes(&self) -> Option<&Vec<u8>> {
-        self.html.as_deref()
+    /// The favicon of the page from the `<link rel="icon">` or `<link rel="apple-touch-icon">` falling back to `/favicon.ico`.
+    pub fn favicon_url(&self) -> Option<Url> {
+        let base = Url::parse(self.get_url_final()).ok()?;
+
+        resolve_favicon_url(&self.head_metadata().icons, &base)
     }
 
-    /// Html getter for bytes on the page as string.
-    pub fn get_html(&self) -> String {
-        self.html
-            .as_ref()
-            .map(|v| auto_encoder::auto_encode_bytes(v))
-            .unwrap_or_default()
+    /// Fetch the bytes of the favicon of the page.
+    pub async fn fetch_favicon(&self, client: &Client) -> Option<bytes::Bytes> {
+        let favicon = self.favicon_url()?;
+
+        match client.get(favicon.as_str()).send().await {
+            Ok(res) if res.status().is_success() => res.bytes().await.ok(),
+            _ => None,
+        }
     }
 
-    /// Html getter for page to u8.
-    pub fn get_html_bytes_u8(&self) -> &[u8] {
-        match self.html.as_deref() {
-            Some(html) => html,
-            _ => Default::default(),
+    /// The title and published date of the feed entry linking to the page when `feed_parsing` is enabled. Only attached to the pages fetched over HTTP.
+    pub fn feed_entry(&self) -> Option<&crate::utils::feed::FeedEntry> {
+        self.feed_entry.as_deref()
+    }
+
+    /// The entries of the page when it is a RSS or Atom feed.
+    pub fn feed_entries(&self) -> Vec<crate::utils::feed::FeedEntry> {
+        let body = self.get_html_bytes();
+
+        if crate::utils::feed::is_feed(&body) {
+            crate::utils::feed::parse_feed_entries(&body)
+        } else {
+            Default::default()
         }
     }
 
@@ -2178,6 +4056,11 @@ impl Page {
     /// Html getter for getting the content with proper encoding. Pass in a proper encoding label like SHIFT_JIS. This fallsback to get_html without the `encoding` flag enabled.
     #[cfg(feature = "encoding")]
     pub fn get_html_encoded(&self, label: &str) -> String {
+        #[cfg(feature = "compress_html")]
+        if self.compressed {
+            return encode_bytes(&self.get_html_bytes().into_owned(), label);
+        }
+
         get_html_encoded(&self.html, label)
     }
 
@@ -2343,7 +4226,7 @@ impl Page {
 
                 element_content_handlers.push(lol_html::element!("base", |el| {
                     if let Some(href) = el.get_attribute("href") {
-                        if let Ok(parsed_base) = Url::parse(&href) {
+                        if let Some(parsed_base) = resolve_base_href(&href, original_page) {
                             let _ = base_input_url.set(parsed_base);
                         }
                     }
@@ -2352,11 +4235,14 @@ impl Page {
                 }));
 
                 element_content_handlers.push(lol_html::element!(
-                    if xml_file {
-                        BASE_CSS_SELECTORS_WITH_XML
-                    } else {
-                        BASE_CSS_SELECTORS
-                    },
+                    scoped_css_selector(
+                        if xml_file {
+                            BASE_CSS_SELECTORS_WITH_XML
+                        } else {
+                            BASE_CSS_SELECTORS
+                        },
+                        self.link_scope.as_deref(),
+                    ),
                     |el| {
                         if let Some(href) = el.get_attribute("href") {
                             let base = if relative_directory_url(&href) || base.is_none() {
@@ -2503,7 +4389,7 @@ impl Page {
 
                 element_content_handlers.push(lol_html::element!("base", |el| {
                     if let Some(href) = el.get_attribute("href") {
-                        if let Ok(parsed_base) = Url::parse(&href) {
+                        if let Some(parsed_base) = resolve_base_href(&href, original_page) {
                             let _ = base_input_url.set(parsed_base);
                         }
                     }
@@ -2512,11 +4398,14 @@ impl Page {
                 }));
 
                 element_content_handlers.push(lol_html::element!(
-                    if xml_file {
-                        BASE_CSS_SELECTORS_WITH_XML
-                    } else {
-                        BASE_CSS_SELECTORS
-                    },
+                    scoped_css_selector(
+                        if xml_file {
+                            BASE_CSS_SELECTORS_WITH_XML
+                        } else {
+                            BASE_CSS_SELECTORS
+                        },
+                        self.link_scope.as_deref(),
+                    ),
                     |el| {
                         if let Some(href) = el.get_attribute("href") {
                             let base = if relative_directory_url(&href) || base.is_none() {
@@ -2679,7 +4568,7 @@ impl Page {
         client: &Client,
         prior_domain: &Option<Box<Url>>,
     ) -> HashSet<A> {
-        if auto_encoder::is_binary_file(self.get_html_bytes_u8()) {
+        if auto_encoder::is_binary_file(&self.get_html_bytes()) {
             Default::default()
         } else {
             self.links_stream_base_ssg(selectors, &Box::new(self.get_html()), client, prior_domain)
@@ -2715,7 +4604,7 @@ impl Page {
         selectors: &RelativeSelectors,
         base: &Option<Box<Url>>,
     ) -> HashSet<A> {
-        if auto_encoder::is_binary_file(self.get_html_bytes_u8()) {
+        if auto_encoder::is_binary_file(&self.get_html_bytes()) {
             Default::default()
         } else {
             self.links_stream_base(selectors, &Box::new(self.get_html()), base)
@@ -2795,7 +4684,8 @@ impl Page {
 
                 element_content_handlers.push(element!("base", |el| {
                     if let Some(href) = el.get_attribute("href") {
-                        if let Ok(parsed_base) = Url::parse(&href) {
+                        if let Some(parsed_base) = resolve_base_href(&href, original_page.as_ref())
+                        {
                             let _ = base_input_url.set(parsed_base);
                         }
                     }
@@ -2865,11 +4755,14 @@ impl Page {
                 }));
 
                 element_content_handlers.push(element!(
-                    if xml_file {
-                        BASE_CSS_SELECTORS_WITH_XML
-                    } else {
-                        BASE_CSS_SELECTORS
-                    },
+                    scoped_css_selector(
+                        if xml_file {
+                            BASE_CSS_SELECTORS_WITH_XML
+                        } else {
+                            BASE_CSS_SELECTORS
+                        },
+                        configuration.link_scope_selector.as_deref(),
+                    ),
                     |el| {
                         if let Some(href) = el.get_attribute("href") {
                             let base = if relative_directory_url(&href) || base.is_none() {
@@ -2981,6 +4874,11 @@ impl Page {
                         })
                         .await
                     {
+                        let _navigation = crate::features::chrome::acquire_navigation(
+                            &browser_controller.navigations,
+                        )
+                        .await;
+
                         if let Ok(new_page) = crate::features::chrome::attempt_navigation(
                             "about:blank",
                             &browser_controller.browser.0,
@@ -2995,6 +4893,7 @@ impl Page {
                                     &new_page,
                                     configuration.chrome_intercept.enabled,
                                     &configuration.auth_challenge_response,
+                                    configuration.chrome_proxy_credentials(),
                                     configuration.chrome_intercept.block_visuals,
                                     &parent_host,
                                 ),
@@ -3172,7 +5071,9 @@ impl Page {
                 let mut element_content_handlers = vec![
                     element!("base", |el| {
                         if let Some(href) = el.get_attribute("href") {
-                            if let Ok(parsed_base) = Url::parse(&href) {
+                            if let Some(parsed_base) =
+                                resolve_base_href(&href, original_page.as_ref())
+                            {
                                 let _ = base_input_url.set(parsed_base);
                             }
                         }
@@ -3234,45 +5135,51 @@ impl Page {
 
                         Ok(())
                     })),
-                    element!("a[href],script[src],link[href]", |el| {
-                        let attribute = if el.tag_name() == "script" {
-                            if !script_found && el.get_attribute("src").is_ok() {
-                                script_found = true;
-                                script_src.store(true, Ordering::Relaxed);
-                            }
-                            "src"
-                        } else {
-                            "href"
-                        };
-                        if let Some(href) = el.get_attribute(attribute) {
-                            let base = if relative_directory_url(&href) || base.is_none() {
-                                original_page.as_ref()
+                    element!(
+                        scoped_css_selector(
+                            "a[href],script[src],link[href]",
+                            configuration.link_scope_selector.as_deref()
+                        ),
+                        |el| {
+                            let attribute = if el.tag_name() == "script" {
+                                if !script_found && el.get_attribute("src").is_ok() {
+                                    script_found = true;
+                                    script_src.store(true, Ordering::Relaxed);
+                                }
+                                "src"
                             } else {
-                                base.as_deref()
+                                "href"
                             };
+                            if let Some(href) = el.get_attribute(attribute) {
+                                let base = if relative_directory_url(&href) || base.is_none() {
+                                    original_page.as_ref()
+                                } else {
+                                    base.as_deref()
+                                };
 
-                            let base = if base_input_url.initialized() {
-                                base_input_url.get()
-                            } else {
-                                base
-                            };
+                                let base = if base_input_url.initialized() {
+                                    base_input_url.get()
+                                } else {
+                                    base
+                                };
 
-                            push_link(
-                                &base,
-                                &href,
-                                &mut inner_map,
-                                &selectors.0,
-                                parent_host,
-                                parent_host_scheme,
-                                base_input_domain,
-                                sub_matcher,
-                                &external_domains_caseless,
-                                &mut links_pages,
-                            );
-                        }
+                                push_link(
+                                    &base,
+                                    &href,
+                                    &mut inner_map,
+                                    &selectors.0,
+                                    parent_host,
+                                    parent_host_scheme,
+                                    base_input_domain,
+                                    sub_matcher,
+                                    &external_domains_caseless,
+                                    &mut links_pages,
+                                );
+                            }
 
-                        Ok(())
-                    }),
+                            Ok(())
+                        }
+                    ),
                     text!("noscript", |el| {
                         if !rerender.load(Ordering::Relaxed) {
                             if NO_SCRIPT_JS_REQUIRED.find(el.as_str()).is_some() {
@@ -3357,6 +5264,11 @@ impl Page {
                         })
                         .await
                     {
+                        let _navigation = crate::features::chrome::acquire_navigation(
+                            &browser_controller.navigations,
+                        )
+                        .await;
+
                         if let Ok(new_page) = crate::features::chrome::attempt_navigation(
                             "about:blank",
                             &browser_controller.browser.0,
@@ -3371,6 +5283,7 @@ impl Page {
                                     &new_page,
                                     configuration.chrome_intercept.enabled,
                                     &configuration.auth_challenge_response,
+                                    configuration.chrome_proxy_credentials(),
                                     configuration.chrome_intercept.block_visuals,
                                     &parent_host,
                                 ),
@@ -3524,8 +5437,12 @@ impl Page {
 
                 let external_domains_caseless = self.external_domains_caseless.clone();
 
-                let base_links_settings =
-                    lol_html::element!("a[href],script[src],link[href]", |el| {
+                let base_links_settings = lol_html::element!(
+                    scoped_css_selector(
+                        "a[href],script[src],link[href]",
+                        self.link_scope.as_deref()
+                    ),
+                    |el| {
                         let attribute = if el.tag_name() == "script" {
                             "src"
                         } else {
@@ -3557,14 +5474,16 @@ impl Page {
                             );
                         }
                         Ok(())
-                    });
+                    }
+                );
 
                 let mut element_content_handlers =
                     metadata_handlers(&mut meta_title, &mut meta_description, &mut meta_og_image);
 
                 element_content_handlers.push(lol_html::element!("base", |el| {
                     if let Some(href) = el.get_attribute("href") {
-                        if let Ok(parsed_base) = Url::parse(&href) {
+                        if let Some(parsed_base) = resolve_base_href(&href, original_page.as_ref())
+                        {
                             let _ = base_input_url.set(parsed_base);
                         }
                     }
@@ -3632,7 +5551,7 @@ impl Page {
         selectors: &RelativeSelectors,
         base: &Option<Box<Url>>,
     ) -> HashSet<A> {
-        if auto_encoder::is_binary_file(self.get_html_bytes_u8()) {
+        if auto_encoder::is_binary_file(&self.get_html_bytes()) {
             Default::default()
         } else {
             self.links_stream_full_resource(selectors, base).await
@@ -3679,7 +5598,7 @@ impl Page {
         match self.html.is_some() {
             false => Default::default(),
             true => {
-                if auto_encoder::is_binary_file(self.get_html_bytes_u8()) {
+                if auto_encoder::is_binary_file(&self.get_html_bytes()) {
                     return Default::default();
                 }
                 self.links_stream_full_resource::<CaseInsensitiveString>(selectors, base)
@@ -3701,7 +5620,7 @@ impl Page {
         match self.html.is_some() {
             false => Default::default(),
             true => {
-                if auto_encoder::is_binary_file(self.get_html_bytes_u8()) {
+                if auto_encoder::is_binary_file(&self.get_html_bytes()) {
                     return Default::default();
                 }
                 self.links_stream_smart::<CaseInsensitiveString>(
@@ -3844,3 +5763,398 @@ async fn test_duration() {
         duration_elasped,
     );
 }
+
+#[test]
+fn test_is_asset_url_query() {
+    assert!(is_asset_url("https://example.com/logo.png?v=1.2.3"));
+    assert!(is_asset_url("https://example.com/logo.png#v1.0"));
+    assert!(!is_asset_url("https://example.com/app?v=1.2.png3"));
+}
+
+#[test]
+fn test_parse_head_metadata() {
+    let html = br#"<html><head><title>Spider</title><meta name="description" content="A web crawler."><link rel="canonical" href="https://example.com/"><meta property="og:title" content="Spider"><meta property="og:type" content="website"></head><body></body></html>"#;
+
+    let head = parse_head_metadata(html);
+
+    assert_eq!(head.title.as_deref(), Some("Spider"));
+    assert_eq!(head.description.as_deref(), Some("A web crawler."));
+    assert_eq!(head.canonical.as_deref(), Some("https://example.com/"));
+    assert_eq!(
+        head.open_graph,
+        vec![
+            ("og:title".into(), "Spider".into()),
+            ("og:type".into(), "website".into())
+        ]
+    );
+}
+
+#[test]
+fn test_open_graph_twitter_card() {
+    let html = br#"<html><head><meta property="og:title" content="Spider"><meta property="og:type" content="article"><meta property="article:published_time" content="2024-01-01"><meta name="twitter:card" content="summary"><meta name="twitter:title" content="Spider"></head></html>"#;
+
+    let head = parse_head_metadata(html);
+    let og = OpenGraph::from_tags(&head.open_graph).unwrap();
+
+    assert_eq!(og.title.as_deref(), Some("Spider"));
+    assert_eq!(og.content_type.as_deref(), Some("article"));
+    assert_eq!(og.published_time.as_deref(), Some("2024-01-01"));
+
+    let card = TwitterCard::from_tags(&head.twitter).unwrap();
+
+    assert_eq!(card.card.as_deref(), Some("summary"));
+    assert_eq!(card.title.as_deref(), Some("Spider"));
+    assert!(TwitterCard::from_tags(&[]).is_none());
+}
+
+#[test]
+fn test_favicon_url() {
+    let base = Url::parse("https://example.com/blog/post").unwrap();
+    let html = br#"<html><head><link rel="apple-touch-icon" href="/apple.png" sizes="180x180"><link rel="shortcut icon" href="small.ico" sizes="16x16"><link rel="icon" href="/icon.png" sizes="32x32 64x64"><link rel="stylesheet" href="/style.css"></head></html>"#;
+
+    let head = parse_head_metadata(html);
+
+    assert_eq!(head.icons.len(), 3);
+    assert_eq!(
+        resolve_favicon_url(&head.icons, &base).unwrap().as_str(),
+        "https://example.com/icon.png"
+    );
+    assert_eq!(
+        resolve_favicon_url(&head.icons[..1], &base)
+            .unwrap()
+            .as_str(),
+        "https://example.com/apple.png"
+    );
+    assert_eq!(
+        resolve_favicon_url(&[], &base).unwrap().as_str(),
+        "https://example.com/favicon.ico"
+    );
+}
+
+#[test]
+fn test_parse_anchors() {
+    let base = Url::parse("https://example.com/blog/post").unwrap();
+    let html = br#"<html><body><a href="/about" title="About us">About
+        <b>the team</b></a><a href="https://other.com/" rel="nofollow ugc">Other</a><a>Missing</a></body></html>"#;
+
+    let anchors = parse_anchors(html, Some(&base));
+
+    assert_eq!(anchors.len(), 2);
+    assert_eq!(anchors[0].href, "https://example.com/about");
+    assert_eq!(anchors[0].text, "About the team");
+    assert_eq!(anchors[0].title.as_deref(), Some("About us"));
+    assert_eq!(anchors[1].href, "https://other.com/");
+    assert!(anchors[1].has_rel("nofollow"));
+    assert!(!anchors[0].has_rel("nofollow"));
+}
+
+#[test]
+fn test_parse_meta_refresh() {
+    assert_eq!(
+        parse_meta_refresh_content("0;url=/next"),
+        Some((0, "/next"))
+    );
+    assert_eq!(
+        parse_meta_refresh_content("5; URL='https://example.com/a b'"),
+        Some((5, "https://example.com/a b"))
+    );
+    assert_eq!(parse_meta_refresh_content("3, /other"), Some((3, "/other")));
+    assert_eq!(parse_meta_refresh_content("10"), None);
+    assert_eq!(parse_meta_refresh_content("soon; url=/next"), None);
+
+    let base = Url::parse("https://example.com/old/").unwrap();
+    let html = br#"<html><head><meta http-equiv="Refresh" content="0; url=../new"></head></html>"#;
+
+    assert_eq!(
+        parse_meta_refresh(html, &base),
+        Some((0, Url::parse("https://example.com/new").unwrap()))
+    );
+    assert_eq!(parse_meta_refresh(b"<html></html>", &base), None);
+}
+
+#[test]
+fn test_resolve_base_href() {
+    let page_url = Url::parse("https://example.com/docs/page").unwrap();
+
+    assert_eq!(
+        resolve_base_href("/app/", Some(&page_url))
+            .unwrap()
+            .as_str(),
+        "https://example.com/app/"
+    );
+    assert_eq!(
+        resolve_base_href("https://cdn.example.com/v2/", Some(&page_url))
+            .unwrap()
+            .as_str(),
+        "https://cdn.example.com/v2/"
+    );
+    assert!(resolve_base_href("/app/", None).is_none());
+
+    let html = br#"<html><head><base href="/app/"></head><body><a href="settings">Settings</a></body></html>"#;
+    let anchors = parse_anchors(html, Some(&page_url));
+
+    assert_eq!(anchors[0].href, "https://example.com/app/settings");
+}
+
+#[test]
+fn test_parse_classified_links() {
+    let base = Url::parse("https://example.com/blog/post").unwrap();
+    let html = br#"<html><head><link rel="stylesheet" href="/main.css"><link rel="canonical" href="/blog/post"><script src="https://cdn.example.net/app.js"></script></head>
+        <body><a href="/about">About</a><a href="https://EXAMPLE.com/contact">Contact</a><a href="https://other.com/">Other</a><a href="mailto:hi@example.com">Mail</a><img src="img/logo.png"></body></html>"#;
+
+    let links = parse_classified_links(html, Some(&base));
+
+    assert_eq!(links.internal.len(), 2);
+    assert!(links
+        .internal
+        .contains(&CaseInsensitiveString::from("https://example.com/about")));
+    assert!(links
+        .internal
+        .contains(&CaseInsensitiveString::from("https://example.com/contact")));
+    assert_eq!(links.external.len(), 1);
+    assert!(links
+        .external
+        .contains(&CaseInsensitiveString::from("https://other.com/")));
+    assert_eq!(links.subresources.len(), 3);
+    assert!(links
+        .subresources
+        .contains(&CaseInsensitiveString::from("https://example.com/main.css")));
+    assert!(links.subresources.contains(&CaseInsensitiveString::from(
+        "https://cdn.example.net/app.js"
+    )));
+    assert!(links.subresources.contains(&CaseInsensitiveString::from(
+        "https://example.com/blog/img/logo.png"
+    )));
+}
+
+#[test]
+fn test_detect_html_encoding() {
+    use auto_encoder::encoding_rs;
+
+    let mut html = br#"<html><head><meta http-equiv="Content-Type" content="text/html; charset=Shift_JIS"></head><body>"#.to_vec();
+    html.extend_from_slice(&[0x93, 0xfa, 0x96, 0x7b]);
+    html.extend_from_slice(b"</body></html>");
+
+    assert_eq!(detect_html_encoding(&html), encoding_rs::SHIFT_JIS);
+    assert_eq!(
+        detect_html_encoding(b"<p>caf\xe9</p>"),
+        encoding_rs::WINDOWS_1252
+    );
+    assert_eq!(
+        detect_html_encoding("<p>café</p>".as_bytes()),
+        encoding_rs::UTF_8
+    );
+
+    let page = build(
+        "https://example.com",
+        PageResponse {
+            content: Some(Box::new(html.clone())),
+            status_code: StatusCode::OK,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.encoding(), Some("Shift_JIS"));
+    assert!(page.get_html().contains("日本"));
+
+    let page = build(
+        "https://example.com",
+        PageResponse {
+            content: Some(Box::new(html)),
+            status_code: StatusCode::OK,
+            charset: Some(encoding_rs::EUC_JP),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.encoding(), Some("EUC-JP"));
+}
+
+#[test]
+fn test_page_redirect_loop() {
+    let page = build(
+        "https://example.com",
+        PageResponse {
+            status_code: StatusCode::LOOP_DETECTED,
+            ..Default::default()
+        },
+    );
+
+    assert!(!page.is_redirect_loop());
+    assert!(page.should_retry);
+
+    let page = build(
+        "https://example.com",
+        PageResponse {
+            status_code: StatusCode::LOOP_DETECTED,
+            redirect_loop: true,
+            ..Default::default()
+        },
+    );
+
+    assert!(page.is_redirect_loop());
+    assert!(!page.should_retry);
+}
+
+#[test]
+fn test_page_text() {
+    let html = r#"<html><head><title>Title</title><style>p { color: red; }</style></head>
+<body><h1>Hello</h1><p>The   quick
+    <b>brown</b> fox.</p><script>var x = "hidden";</script><noscript>No js</noscript><div>Jumps</div></body></html>"#;
+
+    assert_eq!(
+        parse_text(html.as_bytes()),
+        "Hello The quick brown fox. Jumps"
+    );
+
+    let mut page = build(
+        "https://example.com",
+        PageResponse {
+            content: Some(Box::new(html.as_bytes().to_vec())),
+            status_code: StatusCode::OK,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.text(), "Hello The quick brown fox. Jumps");
+
+    page.set_html_bytes(Some(b"<p>Replaced</p>".to_vec()));
+
+    assert_eq!(page.text(), "Replaced");
+}
+
+#[test]
+fn test_page_word_count() {
+    let words = vec!["word"; 476].join(" ");
+    let html = format!("<html><body><p>{words}</p><script>var a = 1;</script></body></html>");
+
+    let page = build(
+        "https://example.com",
+        PageResponse {
+            content: Some(Box::new(html.into_bytes())),
+            status_code: StatusCode::OK,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.word_count(), 476);
+    assert_eq!(page.reading_time(), Duration::from_secs(120));
+    assert_eq!(Page::default().word_count(), 0);
+}
+
+#[test]
+#[cfg(feature = "compress_html")]
+fn test_compress_html() {
+    let html = format!(
+        "<html><body>{}</body></html>",
+        "<p>Hello world</p>".repeat(100)
+    );
+
+    let mut page = build(
+        "https://example.com",
+        PageResponse {
+            content: Some(Box::new(html.clone().into_bytes())),
+            status_code: StatusCode::OK,
+            ..Default::default()
+        },
+    );
+
+    page.compress_html();
+
+    assert!(page.is_compressed());
+    assert!(!page.is_empty());
+    assert_eq!(page.html_len(), html.len());
+    assert_eq!(page.get_html(), html);
+    assert_eq!(page.word_count(), 200);
+    assert_eq!(page.get_html_bytes(), html.as_bytes());
+    assert!(page
+        .get_compressed_bytes()
+        .is_some_and(|bytes| bytes.len() < html.len()));
+    assert_eq!(page.get_html_bytes_u8(), html.as_bytes());
+    assert!(page
+        .get_bytes()
+        .is_some_and(|bytes| bytes == html.as_bytes()));
+
+    page.decompress_html();
+
+    assert!(!page.is_compressed());
+    assert_eq!(page.get_html_bytes_u8(), html.as_bytes());
+}
+
+#[test]
+fn test_needs_rendering() {
+    assert!(needs_rendering(
+        r#"<html><head><script src="/static/js/main.js"></script></head><body><div id="root"></div></body></html>"#
+    ));
+    assert!(needs_rendering(
+        r#"<html><body><noscript>You need to enable JavaScript to run this app.</noscript><div id="app"></div><script>window.__STATE__ = {"a": 1};</script></body></html>"#
+    ));
+    assert!(!needs_rendering(&format!(
+        r#"<html><body><div id="app"><p>{}</p></div><script src="/app.js"></script></body></html>"#,
+        "Server rendered content. ".repeat(20)
+    )));
+    assert!(!needs_rendering("<html><body></body></html>"));
+}
+
+#[tokio::test]
+#[cfg(all(not(feature = "decentralized"), not(feature = "wreq")))]
+async fn test_new_page_settings_response_headers() {
+    use crate::utils::transport::MockTransport;
+
+    let mut settings = PageLinkBuildSettings {
+        transport: Some(std::sync::Arc::new(
+            MockTransport::new().with_html("https://example.com/", "<a href=\"/a\">A</a>"),
+        )),
+        ..Default::default()
+    };
+
+    let client = Client::default();
+    let page = Page::new_page_settings("https://example.com/", &client, &settings).await;
+
+    assert!(page.get_html().contains("href=\"/a\""));
+
+    settings.on_response_headers = Some(std::sync::Arc::new(|_, _| ResponseAction::Abort));
+
+    let page = Page::new_page_settings("https://example.com/", &client, &settings).await;
+
+    assert!(page.is_empty());
+    assert_eq!(page.status_code, StatusCode::OK);
+}
+
+#[tokio::test]
+#[cfg(not(feature = "decentralized"))]
+async fn test_links_link_scope() {
+    let url = "https://example.com/";
+    let mut page = build(
+        url,
+        PageResponse {
+            content: Some(Box::new(
+                b"<html><body><nav><a href=\"/nav\">Nav</a></nav><main><a href=\"/content\">Content</a></main></body></html>".to_vec(),
+            )),
+            status_code: StatusCode::OK,
+            ..Default::default()
+        },
+    );
+    let selector = get_page_selectors(url, false, false);
+
+    page.set_link_scope(Some("main".into()));
+
+    let links = page.links(&selector, &None).await;
+
+    assert!(links.contains(&CaseInsensitiveString::from("https://example.com/content")));
+    assert!(!links.contains(&CaseInsensitiveString::from("https://example.com/nav")));
+}
+
+#[test]
+fn test_parse_pagination_links_dedup() {
+    let base = Url::parse("https://example.com/posts").unwrap();
+    let html = br#"<html><head><link rel="next" href="/posts?page=2"></head><body><a href="/archive">Older posts</a><a rel="next" href="/posts?page=2">Next</a></body></html>"#;
+
+    assert_eq!(
+        parse_pagination_links(html, &base, &PaginationConfig::default()),
+        vec![
+            Url::parse("https://example.com/posts?page=2").unwrap(),
+            Url::parse("https://example.com/archive").unwrap()
+        ]
+    );
+}
@@ -45,6 +45,37 @@ pub(crate) fn parse_absolute_url(url: &str) -> Option<Box<Url>> {
     }
 }
 
+/// Normalize the host of the url converting IDN hosts to punycode and canonicalizing IPv6 literals.
+/// The rest of the url is left untouched so the same host always dedups in the visited set.
+pub(crate) fn normalize_url_host(url: &str) -> std::borrow::Cow<'_, str> {
+    let authority = crate::utils::get_domain_from_url(url);
+    let authority = match authority.find(['?', '#']) {
+        Some(end) => &authority[..end],
+        _ => authority,
+    };
+
+    if authority.is_ascii() && !authority.contains('[') {
+        return std::borrow::Cow::Borrowed(url);
+    }
+
+    match (Url::parse(url), url.find(authority)) {
+        (Ok(u), Some(start)) if u.has_host() => {
+            let normalized = &u[url::Position::BeforeUsername..url::Position::AfterPort];
+
+            if normalized == authority {
+                std::borrow::Cow::Borrowed(url)
+            } else {
+                std::borrow::Cow::Owned(string_concat!(
+                    &url[..start],
+                    normalized,
+                    &url[start + authority.len()..]
+                ))
+            }
+        }
+        _ => std::borrow::Cow::Borrowed(url),
+    }
+}
+
 /// Firewall protection. This does nothing without the [firewall] flag.
 #[cfg(feature = "firewall")]
 pub(crate) fn block_website(u: &Url) -> bool {
@@ -162,7 +193,7 @@ pub(crate) fn convert_abs_path(base: &Url, href: &str) -> Url {
 
 #[cfg(test)]
 mod tests {
-    use super::convert_abs_path;
+    use super::{convert_abs_path, normalize_url_host};
     use crate::utils::parse_absolute_url;
 
     #[test]
@@ -253,4 +284,24 @@ mod tests {
             "Should treat domain-like href as full URL"
         );
     }
+
+    #[test]
+    fn test_normalize_url_host_idn() {
+        assert_eq!(
+            normalize_url_host("https://münchen.de/straße?q=ü"),
+            "https://xn--mnchen-3ya.de/straße?q=ü"
+        );
+        assert_eq!(
+            normalize_url_host("https://xn--mnchen-3ya.de/"),
+            "https://xn--mnchen-3ya.de/"
+        );
+    }
+
+    #[test]
+    fn test_normalize_url_host_ipv6() {
+        assert_eq!(
+            normalize_url_host("http://[0:0:0:0:0:0:0:1]:8080/path"),
+            "http://[::1]:8080/path"
+        );
+    }
 }
@@ -0,0 +1,154 @@
+use std::hash::{BuildHasher, Hasher};
+
+/// The size of the bloom filter for the visited links.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BloomConfig {
+    /// The expected amount of links to visit.
+    pub capacity: usize,
+    /// The rate of the links wrongly reported as visited and skipped, ex: `0.001`.
+    pub fp_rate: f64,
+}
+
+impl BloomConfig {
+    /// A new bloom config for the expected amount of links and the false positive rate.
+    pub fn new(capacity: usize, fp_rate: f64) -> Self {
+        Self { capacity, fp_rate }
+    }
+}
+
+impl Default for BloomConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000_000,
+            fp_rate: 0.001,
+        }
+    }
+}
+
+/// A bloom filter of the links with the case ignored. The links can not be removed or listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    /// The bits of the filter.
+    bits: Vec<u64>,
+    /// The amount of bits of the filter.
+    num_bits: u64,
+    /// The amount of bits set for each link.
+    num_hashes: u32,
+    /// The amount of links inserted.
+    len: usize,
+}
+
+impl BloomFilter {
+    /// A new bloom filter sized for the capacity and false positive rate.
+    pub fn new(config: &BloomConfig) -> Self {
+        let capacity = config.capacity.max(1) as f64;
+        let fp_rate = config.fp_rate.clamp(f64::MIN_POSITIVE, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+
+        let num_bits = (-(capacity * fp_rate.ln()) / (ln2 * ln2)).ceil().max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / capacity) * ln2)
+            .round()
+            .clamp(1.0, 32.0) as u32;
+
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+            len: 0,
+        }
+    }
+
+    /// The two hashes of the link for the double hashing of the bit positions.
+    fn hashes(link: &str) -> (u64, u64) {
+        let hash = |seed: u64| {
+            let mut hasher = ahash::RandomState::with_seeds(seed, 0, 0, 0).build_hasher();
+            for b in link.bytes() {
+                hasher.write_u8(b.to_ascii_lowercase());
+            }
+            hasher.finish()
+        };
+
+        (hash(0x5370_6964_6572), hash(0x42_6c6f_6f6d) | 1)
+    }
+
+    /// The bit positions of the link.
+    fn positions(&self, link: &str) -> impl Iterator<Item = u64> {
+        let (h1, h2) = Self::hashes(link);
+        let num_bits = self.num_bits;
+
+        (0..self.num_hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+    }
+
+    /// Insert the link returning `true` if it was not in the filter.
+    pub fn insert(&mut self, link: &str) -> bool {
+        let mut inserted = false;
+
+        for bit in self.positions(link) {
+            let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+
+            if self.bits[word] & mask == 0 {
+                self.bits[word] |= mask;
+                inserted = true;
+            }
+        }
+
+        if inserted {
+            self.len += 1;
+        }
+
+        inserted
+    }
+
+    /// The link may be in the filter. Links never inserted are reported at the false positive rate.
+    pub fn contains(&self, link: &str) -> bool {
+        self.positions(link)
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// The amount of links inserted.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The filter is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clear the filter.
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter() {
+        let mut bloom = BloomFilter::new(&BloomConfig::new(1_000, 0.01));
+
+        assert!(bloom.insert("https://example.com/a"));
+        assert!(!bloom.insert("https://EXAMPLE.com/a"));
+        assert!(bloom.contains("https://example.com/a"));
+        assert_eq!(bloom.len(), 1);
+
+        for i in 0..1_000 {
+            bloom.insert(&format!("https://example.com/{i}"));
+        }
+
+        let false_positives = (0..1_000)
+            .filter(|i| bloom.contains(&format!("https://other.com/{i}")))
+            .count();
+
+        assert!(false_positives < 50, "{false_positives}");
+
+        bloom.clear();
+
+        assert!(bloom.is_empty());
+        assert!(!bloom.contains("https://example.com/a"));
+    }
+}
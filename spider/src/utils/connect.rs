@@ -208,3 +208,188 @@ where
         }
     }
 }
+
+/// The requests and the new connections opened to a host.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HostConnectionStats {
+    /// The requests sent to the host.
+    pub requests: usize,
+    /// The new connections opened to the host.
+    pub new_connections: usize,
+}
+
+impl HostConnectionStats {
+    /// The requests that re-used a kept alive connection. The connections opened to follow a redirect are counted to the host of the page, a page redirected to a new host opens more connections than requests.
+    pub fn reused(&self) -> usize {
+        self.requests.saturating_sub(self.new_connections)
+    }
+}
+
+/// The connection re-use of the HTTP client by host. Clones share the same counters.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionStats {
+    /// The counters by host.
+    hosts: std::sync::Arc<std::sync::Mutex<hashbrown::HashMap<String, HostConnectionStats>>>,
+}
+
+impl ConnectionStats {
+    /// Update the counters of the host.
+    fn update(&self, host: &str, f: impl FnOnce(&mut HostConnectionStats)) {
+        if let Ok(mut hosts) = self.hosts.lock() {
+            f(hosts.entry_ref(host).or_default());
+        }
+    }
+
+    /// Send the request to the url counting it and the new connections the [`ConnectionCounterLayer`] of the client opens for it.
+    pub(crate) async fn scope<F: Future>(&self, url: &str, request: F) -> F::Output {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from));
+
+        match host {
+            Some(host) => {
+                self.update(&host, |stats| stats.requests += 1);
+                CONNECTION_SCOPE.scope((self.clone(), host), request).await
+            }
+            _ => request.await,
+        }
+    }
+
+    /// Record a new connection opened to the host.
+    fn record_connection(&self, host: &str) {
+        self.update(host, |stats| stats.new_connections += 1);
+    }
+
+    /// The counters of the host.
+    pub fn get(&self, host: &str) -> HostConnectionStats {
+        match self.hosts.lock() {
+            Ok(hosts) => hosts.get(host).copied().unwrap_or_default(),
+            _ => Default::default(),
+        }
+    }
+
+    /// The counters by host.
+    pub fn hosts(&self) -> hashbrown::HashMap<String, HostConnectionStats> {
+        match self.hosts.lock() {
+            Ok(hosts) => hosts.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// The counters of all of the hosts.
+    pub fn total(&self) -> HostConnectionStats {
+        self.hosts()
+            .values()
+            .fold(HostConnectionStats::default(), |total, stats| {
+                HostConnectionStats {
+                    requests: total.requests + stats.requests,
+                    new_connections: total.new_connections + stats.new_connections,
+                }
+            })
+    }
+
+    /// Reset the counters.
+    pub fn clear(&self) {
+        if let Ok(mut hosts) = self.hosts.lock() {
+            hosts.clear();
+        }
+    }
+}
+
+tokio::task_local! {
+    /// The counters and the host of the request sent by the task.
+    static CONNECTION_SCOPE: (ConnectionStats, String);
+}
+
+/// This tower layer counts the connections opened by the client to the host of the request sent in a [`ConnectionStats::scope`]. The connector is only called for a new connection, the requests re-using a pooled connection are not seen.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConnectionCounterLayer;
+
+impl ConnectionCounterLayer {
+    /// A new connection counter layer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for ConnectionCounterLayer {
+    type Service = ConnectionCounter<S>;
+    fn layer(&self, service: S) -> Self::Service {
+        ConnectionCounter { inner: service }
+    }
+}
+
+/// This tower service counts the connections opened by the client to the host of the request sent in a [`ConnectionStats::scope`].
+#[derive(Debug, Clone)]
+pub struct ConnectionCounter<S> {
+    inner: S,
+}
+
+impl<S, Request> Service<Request> for ConnectionCounter<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let _ = CONNECTION_SCOPE.try_with(|(stats, host)| stats.record_connection(host));
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A connector opening a connection for each call.
+    #[derive(Clone)]
+    struct Connector;
+
+    impl Service<()> for Connector {
+        type Response = ();
+        type Error = BoxError;
+        type Future = std::future::Ready<Result<(), BoxError>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: ()) -> Self::Future {
+            std::future::ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_stats() {
+        let stats = ConnectionStats::default();
+        let mut connector = ConnectionCounterLayer::new().layer(Connector);
+
+        stats
+            .scope("https://example.com/a", async { connector.call(()).await })
+            .await
+            .unwrap();
+        stats.scope("https://example.com:8443/b", async {}).await;
+        stats.scope("https://example.com/c", async {}).await;
+        stats
+            .scope("https://other.com/", async { connector.call(()).await })
+            .await
+            .unwrap();
+        connector.call(()).await.unwrap();
+
+        let example = stats.get("example.com");
+
+        assert_eq!(example.requests, 3);
+        assert_eq!(example.new_connections, 1);
+        assert_eq!(example.reused(), 2);
+        assert_eq!(stats.total().requests, 4);
+        assert_eq!(stats.total().reused(), 2);
+        assert_eq!(stats.get("missing.com"), HostConnectionStats::default());
+    }
+}
@@ -164,3 +164,31 @@ pub(crate) const BASE_CSS_SELECTORS_WITH_XML: &str = concat!(
     ":not([href$=\".pptx\"])",
     ":not([href$=\".dump\"])",
 );
+
+/// Scope the link selector to the descendants of the scope selector, ex: `main` to only follow the links inside the `<main>`. The selector is used as is when the scope is invalid.
+pub(crate) fn scoped_css_selector<'a>(
+    selector: &'a str,
+    scope: Option<&str>,
+) -> std::borrow::Cow<'a, str> {
+    match scope.map(str::trim).filter(|scope| !scope.is_empty()) {
+        Some(scope) => {
+            let scoped = scope
+                .split(',')
+                .flat_map(|scope| {
+                    selector
+                        .split(',')
+                        .map(move |selector| string_concat!(scope.trim(), " ", selector.trim()))
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+
+            if scoped.parse::<lol_html::Selector>().is_ok() {
+                scoped.into()
+            } else {
+                log::warn!("invalid link scope selector: {}", scope);
+                selector.into()
+            }
+        }
+        _ => selector.into(),
+    }
+}
@@ -0,0 +1,147 @@
+use crate::utils::url_store::UrlStore;
+use hashbrown::HashSet;
+
+/// The FNV-1a offset basis.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+/// The FNV-1a prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hash the content with FNV-1a. The hash is stable across versions and platforms so it can be persisted as the baseline of the next runs.
+pub fn content_hash(content: &[u8]) -> u64 {
+    content.iter().fold(FNV_OFFSET, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// The pages that changed since the previous crawl.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeSet {
+    /// The urls crawled that were not in the previous crawl.
+    pub added: Vec<String>,
+    /// The urls of the previous crawl that were not found.
+    pub removed: Vec<String>,
+    /// The urls with content that changed since the previous crawl.
+    pub changed: Vec<String>,
+}
+
+impl ChangeSet {
+    /// Nothing changed since the previous crawl.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Diff the content hashes of the pages crawled against the store updating the baseline.
+#[derive(Default)]
+pub(crate) struct ChangeTracker {
+    /// The changes found.
+    changes: ChangeSet,
+    /// The urls with the content hashed.
+    recorded: HashSet<String>,
+    /// The urls reached by the crawl, ex: the pages that failed or the links found past the crawl limit.
+    reached: HashSet<String>,
+    /// Pages of the crawl were missed, the urls not reached are kept in the baseline.
+    incomplete: bool,
+}
+
+impl ChangeTracker {
+    /// Record the content hash of the url crawled.
+    pub(crate) fn record(&mut self, store: &dyn UrlStore, url: &str, hash: u64) {
+        self.reach(url);
+
+        if !self.recorded.insert(url.into()) {
+            return;
+        }
+
+        match store.get(url) {
+            Some(previous) if previous == hash => (),
+            Some(_) => self.changes.changed.push(url.into()),
+            _ => self.changes.added.push(url.into()),
+        }
+
+        store.set(url, hash);
+    }
+
+    /// Mark the url as reached without the content, keeping the baseline of the url.
+    pub(crate) fn reach(&mut self, url: &str) {
+        if !self.reached.contains(url) {
+            self.reached.insert(url.into());
+        }
+    }
+
+    /// Mark the crawl as missing pages, ex: the subscriber lagged behind the crawl.
+    pub(crate) fn incomplete(&mut self) {
+        self.incomplete = true;
+    }
+
+    /// Remove the urls of the baseline that were not reached returning the changes. Nothing is removed when the crawl is incomplete.
+    pub(crate) fn finish(mut self, store: &dyn UrlStore) -> ChangeSet {
+        if !self.incomplete {
+            for url in store.urls() {
+                if !self.reached.contains(&url) {
+                    store.remove(&url);
+                    self.changes.removed.push(url);
+                }
+            }
+        }
+
+        store.flush();
+
+        self.changes.removed.sort();
+        self.changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::url_store::MemoryUrlStore;
+
+    #[test]
+    fn test_change_tracker() {
+        let store = MemoryUrlStore::new();
+        store.set("https://example.com/a", 1);
+        store.set("https://example.com/b", 2);
+        store.set("https://example.com/c", 3);
+        store.set("https://example.com/e", 5);
+
+        let mut tracker = ChangeTracker::default();
+        tracker.record(&store, "https://example.com/a", 1);
+        tracker.record(&store, "https://example.com/b", 20);
+        tracker.record(&store, "https://example.com/d", 4);
+        tracker.reach("https://example.com/e");
+
+        let changes = tracker.finish(&store);
+
+        assert_eq!(changes.added, vec!["https://example.com/d"]);
+        assert_eq!(changes.changed, vec!["https://example.com/b"]);
+        assert_eq!(changes.removed, vec!["https://example.com/c"]);
+        assert_eq!(store.get("https://example.com/b"), Some(20));
+        assert_eq!(store.get("https://example.com/c"), None);
+        assert_eq!(store.get("https://example.com/e"), Some(5));
+    }
+
+    #[test]
+    fn test_change_tracker_incomplete() {
+        let store = MemoryUrlStore::new();
+        store.set("https://example.com/a", 1);
+        store.set("https://example.com/b", 2);
+
+        let mut tracker = ChangeTracker::default();
+        tracker.record(&store, "https://example.com/a", 1);
+        tracker.incomplete();
+
+        let changes = tracker.finish(&store);
+
+        assert!(changes.is_empty());
+        assert_eq!(store.get("https://example.com/b"), Some(2));
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(content_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(content_hash(b"<p>A</p>"), content_hash(b"<p>B</p>"));
+    }
+}
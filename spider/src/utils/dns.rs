@@ -0,0 +1,94 @@
+use hashbrown::HashMap;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// The resolved addresses with the time they were stored.
+type DnsCacheEntries = HashMap<String, (Instant, Vec<SocketAddr>)>;
+
+/// A DNS resolver that re-uses the resolved addresses of a host within the ttl.
+#[derive(Debug, Clone, Default)]
+pub struct CachingResolver {
+    /// How long the resolved addresses are re-used.
+    ttl: Duration,
+    /// The resolved addresses by host.
+    cache: Arc<RwLock<DnsCacheEntries>>,
+}
+
+impl CachingResolver {
+    /// A new caching resolver.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            cache: Default::default(),
+        }
+    }
+
+    /// Get the cached addresses of the host if they have not expired.
+    fn get(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        match self.cache.read() {
+            Ok(cache) => match cache.get(host) {
+                Some((stored, addrs)) if stored.elapsed() < self.ttl => Some(addrs.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Store the resolved addresses of the host.
+    fn insert(&self, host: String, addrs: Vec<SocketAddr>) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.retain(|_, (stored, _)| stored.elapsed() < self.ttl);
+            cache.insert(host, (Instant::now(), addrs));
+        }
+    }
+
+    /// Resolve the host using the cache before the system resolver.
+    async fn lookup(self, host: String) -> Result<Addrs, Box<dyn std::error::Error + Send + Sync>> {
+        let addrs = match self.get(&host) {
+            Some(addrs) => addrs,
+            _ => {
+                let lookup = host.clone();
+                let addrs = tokio::task::spawn_blocking(move || {
+                    (lookup.as_str(), 0)
+                        .to_socket_addrs()
+                        .map(|addrs| addrs.collect::<Vec<_>>())
+                })
+                .await??;
+
+                self.insert(host, addrs.clone());
+
+                addrs
+            }
+        };
+
+        Ok(Box::new(addrs.into_iter()))
+    }
+}
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(self.clone().lookup(name.as_str().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachingResolver;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    #[test]
+    fn test_dns_cache_ttl() {
+        let addrs: Vec<SocketAddr> = vec!["127.0.0.1:0".parse().unwrap()];
+
+        let resolver = CachingResolver::new(Duration::from_secs(60));
+        resolver.insert("example.com".into(), addrs.clone());
+        assert_eq!(resolver.get("example.com"), Some(addrs.clone()));
+
+        let resolver = CachingResolver::new(Duration::ZERO);
+        resolver.insert("example.com".into(), addrs);
+        assert_eq!(resolver.get("example.com"), None);
+    }
+}
@@ -0,0 +1,294 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// The content types of RSS and Atom feeds.
+pub(crate) const FEED_CONTENT_TYPES: [&str; 2] = ["application/rss+xml", "application/atom+xml"];
+
+/// The generic XML content types sniffed for the root element of a feed.
+pub(crate) const XML_CONTENT_TYPES: [&str; 2] = ["application/xml", "text/xml"];
+
+/// The max amount of feed entries kept until the entry pages are crawled.
+const FEED_ENTRIES_LIMIT: usize = 10_000;
+
+/// The feed entries by the link of the entry, attached to the entry pages once crawled.
+pub type FeedEntries = std::sync::Arc<std::sync::Mutex<hashbrown::HashMap<String, FeedEntry>>>;
+
+/// An entry of a RSS or Atom feed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeedEntry {
+    /// The link of the entry.
+    pub link: String,
+    /// The title of the entry.
+    pub title: Option<String>,
+    /// The published date of the entry.
+    pub published: Option<String>,
+}
+
+/// The entry field the text belongs to.
+#[derive(PartialEq)]
+enum FeedField {
+    None,
+    Link,
+    Title,
+    Published,
+}
+
+/// Is the body a RSS or Atom feed. The root element is sniffed skipping the XML declaration, comments, and doctype.
+pub fn is_feed(body: &[u8]) -> bool {
+    let mut reader = Reader::from_reader(body);
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                return matches!(e.local_name().as_ref(), b"rss" | b"feed" | b"RDF")
+            }
+            Ok(Event::Eof) | Err(_) => return false,
+            _ => (),
+        }
+
+        buf.clear();
+    }
+}
+
+/// Is the response a RSS or Atom feed or a generic XML document that may hold one.
+pub(crate) fn is_feed_response(res: &crate::client::Response) -> bool {
+    match res
+        .headers()
+        .get(crate::client::header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+    {
+        Some(content_type) => {
+            let content_type = content_type.to_ascii_lowercase();
+
+            FEED_CONTENT_TYPES
+                .iter()
+                .chain(XML_CONTENT_TYPES.iter())
+                .any(|feed_type| content_type.contains(feed_type))
+        }
+        _ => false,
+    }
+}
+
+/// The links of the feed entries. The entries are kept by link to attach the title and published date to the entry pages.
+pub(crate) fn feed_entry_links(
+    body: &[u8],
+    base: &url::Url,
+    feed_entries: &Option<FeedEntries>,
+) -> Vec<url::Url> {
+    if !is_feed(body) {
+        return Default::default();
+    }
+
+    let mut links = Vec::new();
+
+    for entry in parse_feed_entries(body) {
+        if let Ok(link) = base.join(&entry.link) {
+            if let Some(feed_entries) = feed_entries {
+                if let Ok(mut feed_entries) = feed_entries.lock() {
+                    if feed_entries.len() < FEED_ENTRIES_LIMIT {
+                        feed_entries.insert(link.as_str().into(), entry);
+                    }
+                }
+            }
+            links.push(link);
+        }
+    }
+
+    links
+}
+
+/// Take the feed entry of the page crawled.
+pub(crate) fn take_feed_entry(
+    feed_entries: &Option<FeedEntries>,
+    url: &str,
+) -> Option<Box<FeedEntry>> {
+    feed_entries
+        .as_ref()?
+        .lock()
+        .ok()?
+        .remove(url)
+        .map(Box::new)
+}
+
+/// Parse the entries of a RSS or Atom feed.
+pub fn parse_feed_entries(body: &[u8]) -> Vec<FeedEntry> {
+    let mut reader = Reader::from_reader(body);
+
+    let mut buf = Vec::new();
+    let mut entries = Vec::new();
+    let mut entry: Option<FeedEntry> = None;
+    let mut field = FeedField::None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"item" | b"entry" => entry = Some(FeedEntry::default()),
+                name if entry.is_some() => {
+                    text.clear();
+                    field = match name {
+                        b"link" => {
+                            if let Some(ref mut entry) = entry {
+                                set_atom_link(entry, &e);
+                            }
+                            FeedField::Link
+                        }
+                        b"title" => FeedField::Title,
+                        b"pubDate" | b"published" | b"updated" => FeedField::Published,
+                        _ => FeedField::None,
+                    };
+                }
+                _ => (),
+            },
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"link" => {
+                if let Some(ref mut entry) = entry {
+                    set_atom_link(entry, &e);
+                }
+            }
+            Ok(Event::Text(e)) if field != FeedField::None => {
+                if let Ok(v) = e.decode() {
+                    text.push_str(&v);
+                }
+            }
+            Ok(Event::CData(e)) if field != FeedField::None => {
+                text.push_str(&String::from_utf8_lossy(&e));
+            }
+            Ok(Event::GeneralRef(e)) if field != FeedField::None => {
+                if let Ok(Some(c)) = e.resolve_char_ref() {
+                    text.push(c);
+                } else if let Ok(name) = e.decode() {
+                    if let Some(v) = quick_xml::escape::resolve_predefined_entity(&name) {
+                        text.push_str(v);
+                    }
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"item" | b"entry" => {
+                    if let Some(entry) = entry.take() {
+                        if !entry.link.is_empty() {
+                            entries.push(entry);
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(ref mut entry) = entry {
+                        let value = text.trim();
+
+                        if !value.is_empty() {
+                            match field {
+                                FeedField::Link if entry.link.is_empty() => {
+                                    entry.link = value.into()
+                                }
+                                FeedField::Title => entry.title = Some(value.into()),
+                                FeedField::Published if entry.published.is_none() => {
+                                    entry.published = Some(value.into())
+                                }
+                                _ => (),
+                            }
+                        }
+                    }
+                    field = FeedField::None;
+                    text.clear();
+                }
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => (),
+        }
+
+        buf.clear();
+    }
+
+    entries
+}
+
+/// Set the link of the entry from the atom `href` attribute skipping links that are not the alternate.
+fn set_atom_link(entry: &mut FeedEntry, e: &quick_xml::events::BytesStart) {
+    let mut href = None;
+    let mut alternate = true;
+
+    for attr in e.attributes().flatten() {
+        match attr.key.local_name().as_ref() {
+            b"href" => href = Some(String::from_utf8_lossy(&attr.value).into_owned()),
+            b"rel" => alternate = attr.value.as_ref() == b"alternate",
+            _ => (),
+        }
+    }
+
+    if let Some(href) = href {
+        if alternate && entry.link.is_empty() {
+            entry.link = href;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{feed_entry_links, is_feed, parse_feed_entries, take_feed_entry};
+
+    #[test]
+    fn test_parse_rss_entries() {
+        let rss = br#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Blog</title><link>https://example.com</link>
+<item><title><![CDATA[First & post]]></title><link>https://example.com/first</link><pubDate>Mon, 06 Sep 2021 16:45:00 GMT</pubDate></item>
+<item><title>Second &amp; post</title><link>https://example.com/second</link></item>
+</channel></rss>"#;
+
+        assert!(is_feed(rss));
+
+        let entries = parse_feed_entries(rss);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].link, "https://example.com/first");
+        assert_eq!(entries[0].title.as_deref(), Some("First & post"));
+        assert_eq!(
+            entries[0].published.as_deref(),
+            Some("Mon, 06 Sep 2021 16:45:00 GMT")
+        );
+        assert_eq!(entries[1].title.as_deref(), Some("Second & post"));
+    }
+
+    #[test]
+    fn test_parse_atom_entries() {
+        let atom = br#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Blog</title>
+<entry><title>Post</title><link rel="edit" href="https://example.com/edit"/><link href="https://example.com/post"/><published>2021-09-06T16:45:00Z</published></entry>
+</feed>"#;
+
+        assert!(is_feed(atom));
+        assert!(!is_feed(
+            br#"<?xml version="1.0"?><!-- <rss> --><urlset><url><loc>https://example.com/</loc></url></urlset>"#
+        ));
+
+        let entries = parse_feed_entries(atom);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].link, "https://example.com/post");
+        assert_eq!(
+            entries[0].published.as_deref(),
+            Some("2021-09-06T16:45:00Z")
+        );
+    }
+
+    #[test]
+    fn test_feed_entry_links() {
+        let rss = br#"<rss version="2.0"><channel>
+<item><title>Post</title><link>/post</link><pubDate>Mon, 06 Sep 2021 16:45:00 GMT</pubDate></item>
+</channel></rss>"#;
+        let base = url::Url::parse("https://example.com/feed.xml").unwrap();
+        let feed_entries = Some(Default::default());
+
+        let links = feed_entry_links(rss, &base, &feed_entries);
+
+        assert_eq!(links, vec![base.join("/post").unwrap()]);
+
+        let entry = take_feed_entry(&feed_entries, "https://example.com/post").expect("entry");
+
+        assert_eq!(entry.title.as_deref(), Some("Post"));
+        assert!(take_feed_entry(&feed_entries, "https://example.com/post").is_none());
+        assert!(
+            feed_entry_links(b"<html><a href=\"/a\">A</a></html>", &base, &feed_entries).is_empty()
+        );
+    }
+}
@@ -75,6 +75,94 @@ pub fn extend_headers(
     }
 }
 
+/// The navigation headers of a real browser in the browser order matching the user-agent, ex: the `Sec-CH-UA` client hints, `Accept`, and `Sec-Fetch-*`. The headers already set are skipped. Only sent with the page requests, not with the robots.txt or sitemap requests.
+pub fn realistic_headers(header_map: &HeaderMap, user_agent: &str) -> HeaderMap {
+    let chromium = user_agent.contains("Chrome/") && !user_agent.contains("Firefox/");
+
+    let platform = if user_agent.contains("Windows") {
+        "\"Windows\""
+    } else if user_agent.contains("Android") {
+        "\"Android\""
+    } else if user_agent.contains("iPhone") || user_agent.contains("iPad") {
+        "\"iOS\""
+    } else if user_agent.contains("Mac OS X") {
+        "\"macOS\""
+    } else {
+        "\"Linux\""
+    };
+
+    let mobile = if user_agent.contains("Mobile") {
+        "?1"
+    } else {
+        "?0"
+    };
+
+    let mut realistic: Vec<(&str, Option<HeaderValue>)> = Vec::with_capacity(12);
+
+    if chromium {
+        let version = user_agent
+            .split("Chrome/")
+            .nth(1)
+            .and_then(|version| version.split('.').next())
+            .filter(|version| version.bytes().all(|b| b.is_ascii_digit()))
+            .unwrap_or("120");
+        let brand = if user_agent.contains("Edg/") {
+            "Microsoft Edge"
+        } else {
+            "Google Chrome"
+        };
+
+        realistic.push((
+            "sec-ch-ua",
+            HeaderValue::from_str(&format!(
+                "\"Chromium\";v=\"{version}\", \"{brand}\";v=\"{version}\", \"Not-A.Brand\";v=\"99\""
+            ))
+            .ok(),
+        ));
+        realistic.push(("sec-ch-ua-mobile", Some(HeaderValue::from_static(mobile))));
+        realistic.push((
+            "sec-ch-ua-platform",
+            Some(HeaderValue::from_static(platform)),
+        ));
+    }
+
+    realistic.push((
+        "upgrade-insecure-requests",
+        Some(HeaderValue::from_static("1")),
+    ));
+    realistic.push(("user-agent", HeaderValue::from_str(user_agent).ok()));
+    realistic.push((
+        "accept",
+        Some(HeaderValue::from_static(if chromium {
+            "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7"
+        } else {
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"
+        })),
+    ));
+    realistic.extend([
+        ("sec-fetch-site", Some(HeaderValue::from_static("none"))),
+        ("sec-fetch-mode", Some(HeaderValue::from_static("navigate"))),
+        ("sec-fetch-user", Some(HeaderValue::from_static("?1"))),
+        ("sec-fetch-dest", Some(HeaderValue::from_static("document"))),
+        (
+            "accept-language",
+            Some(HeaderValue::from_static("en-US,en;q=0.9")),
+        ),
+    ]);
+
+    let mut headers = HeaderMap::with_capacity(realistic.len());
+
+    for (name, value) in realistic {
+        if let Some(value) = value {
+            if !header_map.contains_key(name) {
+                headers.insert(name, value);
+            }
+        }
+    }
+
+    headers
+}
+
 /// Headers has ref
 pub fn has_ref(headers: &std::option::Option<Box<SerializableHeaderMap>>) -> bool {
     match headers {
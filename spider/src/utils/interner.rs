@@ -1,3 +1,4 @@
+use crate::utils::bloom::{BloomConfig, BloomFilter};
 use crate::CaseInsensitiveString;
 use hashbrown::HashSet;
 use std::hash::Hash;
@@ -57,6 +58,8 @@ where
 {
     pub(crate) links_visited: HashSet<SymbolUsize>,
     pub(crate) interner: StringInterner<Backend>,
+    /// The bloom filter used in place of the links when set.
+    pub(crate) bloom: Option<Box<BloomFilter>>,
     _marker: PhantomData<K>,
 }
 
@@ -72,6 +75,8 @@ where
     K: Eq + Hash + Clone + AsRef<str>,
 {
     pub(crate) links_visited: HashSet<K>,
+    /// The bloom filter used in place of the links when set.
+    pub(crate) bloom: Option<Box<BloomFilter>>,
 }
 
 #[cfg(not(any(
@@ -86,6 +91,7 @@ where
     fn default() -> Self {
         Self {
             links_visited: HashSet::new(),
+            bloom: None,
         }
     }
 }
@@ -103,6 +109,7 @@ where
         Self {
             links_visited: HashSet::new(),
             interner: StringInterner::new(),
+            bloom: None,
             _marker: PhantomData,
         }
     }
@@ -117,9 +124,61 @@ where
         Self::default()
     }
 
+    /// Track the links with a bloom filter instead of storing them. The links already visited are moved to the filter. The links can not be listed or removed after.
+    pub fn with_bloom(&mut self, config: &BloomConfig) {
+        if self.bloom.is_none() {
+            let mut bloom = BloomFilter::new(config);
+
+            for link in self.get_links_base() {
+                bloom.insert(&link);
+            }
+
+            self.links_visited.clear();
+            self.bloom = Some(Box::new(bloom));
+        }
+    }
+
+    /// The links are tracked with the bloom filter and can not be listed or removed.
+    pub fn is_bloom(&self) -> bool {
+        self.bloom.is_some()
+    }
+
+    /// The links of the bucket as strings.
+    fn get_links_base(&self) -> Vec<String> {
+        #[cfg(any(
+            feature = "string_interner_bucket_backend",
+            feature = "string_interner_string_backend",
+            feature = "string_interner_buffer_backend",
+        ))]
+        {
+            self.links_visited
+                .iter()
+                .filter_map(|symbol| self.interner.resolve(*symbol))
+                .map(|s| s.to_string())
+                .collect()
+        }
+
+        #[cfg(not(any(
+            feature = "string_interner_bucket_backend",
+            feature = "string_interner_string_backend",
+            feature = "string_interner_buffer_backend",
+        )))]
+        {
+            self.links_visited
+                .iter()
+                .map(|link| link.as_ref().to_string())
+                .collect()
+        }
+    }
+
     /// Add a new link to the bucket.
     #[inline(always)]
     pub fn insert(&mut self, link: K) {
+        if let Some(bloom) = self.bloom.as_mut() {
+            bloom.insert(link.as_ref());
+            return;
+        }
+
         #[cfg(any(
             feature = "string_interner_bucket_backend",
             feature = "string_interner_string_backend",
@@ -143,6 +202,10 @@ where
     /// Does the bucket contain the link.
     #[inline(always)]
     pub fn contains(&self, link: &K) -> bool {
+        if let Some(bloom) = self.bloom.as_ref() {
+            return bloom.contains(link.as_ref());
+        }
+
         #[cfg(any(
             feature = "string_interner_bucket_backend",
             feature = "string_interner_string_backend",
@@ -166,14 +229,47 @@ where
         }
     }
 
+    /// Remove a link from the bucket. Always returns `false` with the bloom filter since the links can not be removed, check [`ListBucket::is_bloom`] first.
+    #[inline(always)]
+    pub fn remove(&mut self, link: &K) -> bool {
+        if self.bloom.is_some() {
+            return false;
+        }
+
+        #[cfg(any(
+            feature = "string_interner_bucket_backend",
+            feature = "string_interner_string_backend",
+            feature = "string_interner_buffer_backend",
+        ))]
+        {
+            if let Some(symbol) = self.interner.get(link.as_ref()) {
+                self.links_visited.remove(&symbol)
+            } else {
+                false
+            }
+        }
+
+        #[cfg(not(any(
+            feature = "string_interner_bucket_backend",
+            feature = "string_interner_string_backend",
+            feature = "string_interner_buffer_backend",
+        )))]
+        {
+            self.links_visited.remove(link)
+        }
+    }
+
     /// The bucket length.
     pub fn len(&self) -> usize {
-        self.links_visited.len()
+        match self.bloom.as_ref() {
+            Some(bloom) => bloom.len(),
+            _ => self.links_visited.len(),
+        }
     }
 
     /// The bucket is empty.
     pub fn is_empty(&self) -> bool {
-        self.links_visited.is_empty()
+        self.len() == 0
     }
 
     /// Drain the bucket.
@@ -198,10 +294,13 @@ where
 
     /// Clear the bucket.
     pub fn clear(&mut self) {
+        if let Some(bloom) = self.bloom.as_mut() {
+            bloom.clear();
+        }
         self.links_visited.clear()
     }
 
-    /// Get a vector of all the inner values of the links in the bucket.
+    /// Get all of the links in the bucket. The links are not stored with the bloom filter so this is always empty when [`ListBucket::is_bloom`] is `true`.
     pub fn get_links(&self) -> HashSet<K>
     where
         K: Hash + Clone + From<String>,
@@ -235,6 +334,14 @@ where
     where
         K: Clone,
     {
+        if let Some(bloom) = self.bloom.as_ref() {
+            links.extend(
+                msg.into_iter()
+                    .filter(|link| !bloom.contains(link.as_ref())),
+            );
+            return;
+        }
+
         #[cfg(any(
             feature = "string_interner_bucket_backend",
             feature = "string_interner_string_backend",
@@ -265,6 +372,13 @@ where
     where
         K: Clone,
     {
+        if let Some(bloom) = self.bloom.as_ref() {
+            if !bloom.contains(s.as_ref()) {
+                links.insert(s);
+            }
+            return;
+        }
+
         #[cfg(any(
             feature = "string_interner_bucket_backend",
             feature = "string_interner_string_backend",
@@ -1,5 +1,7 @@
 /// Absolute path domain handling.
 pub mod abs;
+/// A bloom filter for the links visited on huge crawls.
+pub mod bloom;
 /// Connect layer for reqwest.
 pub mod connect;
 /// Generic CSS selectors.
@@ -7,14 +9,36 @@ pub mod css_selectors;
 #[cfg(any(feature = "balance", feature = "disk"))]
 /// CPU and Memory detection to balance limitations.
 pub mod detect_system;
+/// Compare the content of the pages against the previous crawl.
+pub mod diff;
+#[cfg(not(feature = "wreq"))]
+/// In process DNS caching.
+pub mod dns;
+/// RSS and Atom feed parsing.
+pub mod feed;
 /// Utils to modify the HTTP header.
 pub mod header_utils;
 /// String interner.
 pub mod interner;
+/// Limit the requests per second by host across crawls.
+pub mod rate_limiter;
+/// Track the time the urls were last crawled for recrawls.
+pub mod recrawl;
+/// Summarize the pages of a crawl.
+pub mod report;
+/// Cache the robots.txt files by host across crawls.
+pub mod robots_cache;
+/// Serve the HTTP responses without the network.
+pub mod transport;
 /// A trie struct.
 pub mod trie;
+/// Store a value by url across crawls.
+pub mod url_store;
 /// Validate html false positives.
 pub mod validation;
+#[cfg(feature = "warc")]
+/// Read and write WARC web archives.
+pub mod warc;
 
 use crate::{
     page::{AntiBotTech, Metadata, STREAMING_CHUNK_SIZE},
@@ -251,6 +275,23 @@ lazy_static! {
         .expect("valid CF just-a-moment patterns");
 }
 
+/// A random number in the range. Uses the randomly keyed std hasher to avoid requiring a rng.
+pub(crate) fn random_in_range(range: &std::ops::Range<u64>) -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    if range.is_empty() {
+        return range.start;
+    }
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+
+    if let Ok(now) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        hasher.write_u128(now.as_nanos());
+    }
+
+    range.start + hasher.finish() % (range.end - range.start)
+}
+
 #[cfg(all(feature = "chrome", feature = "real_browser"))]
 #[inline]
 /// Is turnstile page? This does nothing without the 'real_browser' feature enabled.
@@ -468,6 +509,8 @@ pub struct PageResponse {
     pub content: Option<Box<Vec<u8>>>,
     /// The headers of the response. (Always None if a webdriver protocol is used for fetching.).
     pub headers: Option<reqwest::header::HeaderMap>,
+    /// The encoding of the charset in the content type of the response.
+    pub charset: Option<&'static auto_encoder::encoding_rs::Encoding>,
     #[cfg(feature = "remote_addr")]
     /// The remote address of the page.
     pub remote_addr: Option<core::net::SocketAddr>,
@@ -495,6 +538,12 @@ pub struct PageResponse {
     pub bytes_transferred: Option<f64>,
     /// The signature of the page to use for handling de-duplication.
     pub signature: Option<u64>,
+    /// The amount of redirects followed.
+    pub redirect_count: usize,
+    /// The response was served from a cache instead of the network.
+    pub from_cache: bool,
+    /// The request was stopped by a redirect chain looping back to a url that was already requested.
+    pub redirect_loop: bool,
     #[cfg(feature = "chrome")]
     /// All of the response events mapped with the amount of bytes used.
     pub response_map: Option<HashMap<String, f64>>,
@@ -2762,6 +2811,7 @@ pub async fn fetch_page_html_chrome_base(
         };
 
         if content {
+            page_response.from_cache = true;
             if let Some(final_url) = &page_response.final_url {
                 if final_url.starts_with("about:blank") {
                     page_response.final_url = None;
@@ -2927,6 +2977,11 @@ pub async fn fetch_page_html_chrome_base(
         {
             let response_map = rs.response_map;
 
+            #[cfg(feature = "cache_request")]
+            if rs.main_doc_from_cache {
+                page_response.from_cache = true;
+            }
+
             if response_map.is_some() {
                 let mut _response_map = HashMap::new();
 
@@ -3326,6 +3381,83 @@ pub(crate) fn block_streaming(res: &Response, only_html: bool) -> bool {
     block_streaming
 }
 
+/// The response content type is missing or generic binary, ex: `application/octet-stream`.
+pub(crate) fn generic_content_type(res: &Response) -> bool {
+    match res.headers().get(crate::client::header::CONTENT_TYPE) {
+        Some(content_type) => content_type.to_str().map_or(true, |content_type| {
+            let mime = content_type.split(';').next().unwrap_or_default().trim();
+
+            mime.is_empty()
+                || mime.eq_ignore_ascii_case("application/octet-stream")
+                || mime.eq_ignore_ascii_case("binary/octet-stream")
+                || mime.eq_ignore_ascii_case("application/unknown")
+        }),
+        _ => true,
+    }
+}
+
+/// Run the response headers callback determining if the body download should be aborted.
+pub(crate) fn abort_on_response_headers(
+    res: &Response,
+    on_response_headers: &Option<crate::page::OnResponseHeadersCallback>,
+) -> bool {
+    match on_response_headers {
+        Some(cb) => cb(res.headers(), res.url()) == crate::page::ResponseAction::Abort,
+        _ => false,
+    }
+}
+
+/// Find the custom link extractor matching the content type of the response.
+pub(crate) fn find_link_extractor(
+    res: &Response,
+    link_extractors: &Option<crate::page::LinkExtractors>,
+) -> Option<crate::page::LinkExtractor> {
+    let content_type = res
+        .headers()
+        .get(crate::client::header::CONTENT_TYPE)?
+        .to_str()
+        .ok()?
+        .to_ascii_lowercase();
+
+    link_extractors.as_ref().and_then(|link_extractors| {
+        link_extractors
+            .iter()
+            .find(|(pattern, _)| content_type.contains(&pattern.to_ascii_lowercase()))
+            .map(|(_, extractor)| extractor.clone())
+    })
+}
+
+/// Handle the response without downloading the body.
+pub(crate) fn handle_response_headers_only(res: Response, target_url: &str) -> PageResponse {
+    let u = res.url().as_str();
+
+    let final_url = if target_url != u {
+        Some(u.into())
+    } else {
+        None
+    };
+
+    let headers = res.headers().clone();
+
+    PageResponse {
+        anti_bot_tech: detect_anti_bot_tech_response(
+            target_url,
+            &HeaderSource::HeaderMap(&headers),
+            &Default::default(),
+            None,
+        ),
+        #[cfg(feature = "headers")]
+        headers: Some(headers),
+        #[cfg(feature = "remote_addr")]
+        remote_addr: res.remote_addr(),
+        #[cfg(feature = "cookies")]
+        cookies: get_cookies(&res),
+        final_url,
+        status_code: res.status(),
+        ..Default::default()
+    }
+}
+
 /// Handle the response bytes
 pub async fn handle_response_bytes(
     res: Response,
@@ -3342,9 +3474,11 @@ pub async fn handle_response_bytes(
 
     let status_code: StatusCode = res.status();
     let headers = res.headers().clone();
+    let from_cache = is_cache_hit(&headers);
     #[cfg(feature = "remote_addr")]
     let remote_addr = res.remote_addr();
     let cookies = get_cookies(&res);
+    let charset = crate::page::get_encoding_from_content_type(&headers);
 
     let mut content: Option<Box<Vec<u8>>> = None;
     let mut anti_bot_tech = AntiBotTech::default();
@@ -3439,13 +3573,26 @@ pub async fn handle_response_bytes(
         #[cfg(feature = "cookies")]
         cookies,
         content,
+        charset,
         final_url: rd,
         status_code,
+        from_cache,
         anti_bot_tech,
         ..Default::default()
     }
 }
 
+/// The options of the response bytes writer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResponseWriterOptions<'a> {
+    /// Send the body chunks as they arrive.
+    pub chunks: Option<&'a tokio::sync::broadcast::Sender<crate::page::PageChunk>>,
+    /// Stop writing once the rewriter found the end of the head.
+    pub head_ended: Option<&'a std::sync::atomic::AtomicBool>,
+    /// Sniff the leading bytes of the responses with a missing, generic, or ignored content type.
+    pub mime_sniffing: bool,
+}
+
 /// Handle the response bytes writing links while crawling
 pub async fn handle_response_bytes_writer<'h, O>(
     res: Response,
@@ -3453,6 +3600,7 @@ pub async fn handle_response_bytes_writer<'h, O>(
     only_html: bool,
     rewriter: &mut HtmlRewriter<'h, O>,
     collected_bytes: &mut Vec<u8>,
+    options: ResponseWriterOptions<'_>,
 ) -> (PageResponse, bool)
 where
     O: OutputSink + Send + 'static,
@@ -3467,22 +3615,61 @@ where
 
     let status_code: StatusCode = res.status();
     let headers = res.headers().clone();
+    let from_cache = is_cache_hit(&headers);
     #[cfg(feature = "remote_addr")]
     let remote_addr = res.remote_addr();
     let cookies = get_cookies(&res);
+    let charset = crate::page::get_encoding_from_content_type(&headers);
     let mut anti_bot_tech = AntiBotTech::default();
 
     let mut rewrite_error = false;
+    let blocked = block_streaming(&res, only_html);
+    let sniff = options.mime_sniffing && (blocked || generic_content_type(&res));
 
-    if !block_streaming(&res, only_html) {
+    if sniff || !blocked {
         let mut stream = res.bytes_stream();
         let mut first_bytes = true;
         let mut data_len = 0;
+        // the leading bytes are held until enough arrived to sniff the content.
+        let mut sniff_buffer: Option<Vec<u8>> = if sniff { Some(Vec::new()) } else { None };
+        // the sniffed content is not html, the bytes are kept without parsing the links.
+        let mut sniffed_other = false;
+
+        loop {
+            let item = match stream.next().await {
+                Some(Ok(res_bytes)) => match sniff_buffer.as_mut() {
+                    Some(buffer) => {
+                        buffer.extend_from_slice(&res_bytes);
+                        if buffer.len() < MIME_SNIFF_LENGTH {
+                            continue;
+                        }
+                        let buffer = sniff_buffer.take().unwrap_or_default();
+                        sniffed_other = !is_html_content_check(&buffer);
+                        // the ignored content types are only rescued when the content is html.
+                        if blocked && sniffed_other {
+                            break;
+                        }
+                        Some(Ok((bytes::Bytes::from(buffer), false)))
+                    }
+                    _ => Some(Ok((res_bytes, false))),
+                },
+                Some(Err(e)) => Some(Err(e)),
+                _ => match sniff_buffer.take() {
+                    Some(buffer) => {
+                        sniffed_other = !is_html_content_check(&buffer);
+                        if blocked && sniffed_other {
+                            None
+                        } else {
+                            Some(Ok((bytes::Bytes::from(buffer), true)))
+                        }
+                    }
+                    _ => None,
+                },
+            };
 
-        while let Some(item) = stream.next().await {
             match item {
-                Ok(res_bytes) => {
-                    if only_html && first_bytes {
+                Some(Ok((res_bytes, ended))) => {
+                    if only_html && first_bytes && (!sniff || sniffed_other) {
                         first_bytes = false;
                         if is_binary_file(&res_bytes) {
                             break;
@@ -3497,21 +3684,47 @@ where
 
                     data_len += bytes_len;
 
-                    if !rewrite_error {
+                    if !rewrite_error && !sniffed_other {
                         if rewriter.write(&res_bytes).is_err() {
                             rewrite_error = true;
                         }
                     }
 
                     collected_bytes.extend_from_slice(&res_bytes);
+
+                    if let Some(tx) = options.chunks {
+                        let _ = tx.send(crate::page::PageChunk::Chunk {
+                            url: target_url.into(),
+                            bytes: res_bytes,
+                        });
+                    }
+
+                    // stop once the rewriter found the end of the head.
+                    if options
+                        .head_ended
+                        .is_some_and(|h| h.load(std::sync::atomic::Ordering::Relaxed))
+                    {
+                        break;
+                    }
+
+                    if ended {
+                        break;
+                    }
                 }
-                Err(e) => {
+                Some(Err(e)) => {
                     log::error!("{e} in {}", target_url);
                     break;
                 }
+                _ => break,
             }
         }
 
+        if let Some(tx) = options.chunks {
+            let _ = tx.send(crate::page::PageChunk::Complete {
+                url: target_url.into(),
+            });
+        }
+
         anti_bot_tech = detect_anti_bot_tech_response(
             &target_url,
             &HeaderSource::HeaderMap(&headers),
@@ -3528,8 +3741,10 @@ where
             remote_addr,
             #[cfg(feature = "cookies")]
             cookies,
+            charset,
             final_url,
             status_code,
+            from_cache,
             anti_bot_tech,
             ..Default::default()
         },
@@ -3537,6 +3752,41 @@ where
     )
 }
 
+/// The response was served by the http cache layer. The cache middleware marks hits with the `x-cache` header, the upstream values are removed by [`UpstreamCacheStatus`].
+#[cfg(feature = "cache_request")]
+pub(crate) fn is_cache_hit(headers: &crate::client::header::HeaderMap) -> bool {
+    headers
+        .get("x-cache")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("HIT"))
+}
+
+/// The response was served by the http cache layer. This is always false without the `cache_request` flag enabled.
+#[cfg(not(feature = "cache_request"))]
+pub(crate) fn is_cache_hit(_headers: &crate::client::header::HeaderMap) -> bool {
+    false
+}
+
+/// Remove the cache status headers of the upstream caches from the network responses, ex: a CDN `x-cache: HIT`. Add the middleware after the http cache so only the local cache marks the hits.
+#[cfg(feature = "cache_request")]
+pub(crate) struct UpstreamCacheStatus;
+
+#[cfg(feature = "cache_request")]
+#[async_trait::async_trait]
+impl reqwest_middleware::Middleware for UpstreamCacheStatus {
+    async fn handle(
+        &self,
+        req: reqwest::Request,
+        extensions: &mut http::Extensions,
+        next: reqwest_middleware::Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        let mut res = next.run(req, extensions).await?;
+        res.headers_mut().remove("x-cache");
+        res.headers_mut().remove("x-cache-lookup");
+        Ok(res)
+    }
+}
+
 /// Continue to parse a valid web page.
 pub(crate) fn valid_parsing_status(res: &Response) -> bool {
     res.status().is_success() || res.status() == 404
@@ -5398,6 +5648,73 @@ where
     set.spawn(future)
 }
 
+/// Wait for the tasks of the set to finish up to the timeout before aborting the remaining tasks.
+pub(crate) async fn shutdown_set<T: 'static>(
+    set: &mut tokio::task::JoinSet<T>,
+    timeout: &Option<Duration>,
+) {
+    if let Some(timeout) = timeout {
+        let _ = tokio::time::timeout(*timeout, async { while set.join_next().await.is_some() {} })
+            .await;
+    }
+    set.shutdown().await;
+}
+
+/// The shutdown state shared by the clones of a website, ex: to stop a crawl running on another task.
+#[derive(Debug, Clone)]
+pub(crate) struct ShutdownSignal {
+    /// The shutdown was requested.
+    requested: Arc<tokio::sync::watch::Sender<bool>>,
+    /// The amount of crawls running.
+    running: Arc<tokio::sync::watch::Sender<usize>>,
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        Self {
+            requested: Arc::new(tokio::sync::watch::channel(false).0),
+            running: Arc::new(tokio::sync::watch::channel(0).0),
+        }
+    }
+}
+
+impl ShutdownSignal {
+    /// Request or reset the shutdown.
+    pub(crate) fn set(&self, requested: bool) {
+        self.requested.send_replace(requested);
+    }
+
+    /// The shutdown was requested.
+    pub(crate) fn is_set(&self) -> bool {
+        *self.requested.borrow()
+    }
+
+    /// Wait for the shutdown to be requested.
+    pub(crate) async fn requested(&self) {
+        let _ = self.requested.subscribe().wait_for(|s| *s).await;
+    }
+
+    /// Mark a crawl running until the guard drops.
+    pub(crate) fn running(&self) -> RunningGuard {
+        self.running.send_modify(|n| *n += 1);
+        RunningGuard(self.running.clone())
+    }
+
+    /// Wait for the running crawls to return.
+    pub(crate) async fn idle(&self) {
+        let _ = self.running.subscribe().wait_for(|n| *n == 0).await;
+    }
+}
+
+/// Marks a crawl running for the [`ShutdownSignal`].
+pub(crate) struct RunningGuard(Arc<tokio::sync::watch::Sender<usize>>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.send_modify(|n| *n = n.saturating_sub(1));
+    }
+}
+
 #[cfg(feature = "balance")]
 /// Period to wait to rebalance cpu in means of IO being main impact.
 const REBALANCE_TIME: std::time::Duration = std::time::Duration::from_millis(100);
@@ -5436,10 +5753,13 @@ static HTML_TAGS: phf::Set<&'static [u8]> = phf_set! {
     b"<document",
 };
 
+/// The amount of leading bytes inspected to sniff the html content.
+pub(crate) const MIME_SNIFF_LENGTH: usize = 1024;
+
 /// Check if the content is HTML.
 pub fn is_html_content_check(bytes: &[u8]) -> bool {
-    let check_bytes = if bytes.len() > 1024 {
-        &bytes[..1024]
+    let check_bytes = if bytes.len() > MIME_SNIFF_LENGTH {
+        &bytes[..MIME_SNIFF_LENGTH]
     } else {
         bytes
     };
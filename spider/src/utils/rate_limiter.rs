@@ -0,0 +1,103 @@
+use hashbrown::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The hosts kept before the idle hosts are evicted.
+const IDLE_HOSTS_LIMIT: usize = 1024;
+
+/// A requests per second limit by host shared across crawls. Clones share the same limits so one limiter can be passed to multiple websites.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// The time between the requests to a host.
+    interval: Duration,
+    /// The next time a request is allowed by host. The hosts without a pending slot are evicted once the limit is reached.
+    hosts: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl RateLimiter {
+    /// A new rate limiter allowing the requests per second for each host.
+    pub fn new(requests_per_second: f64) -> Self {
+        let interval = if requests_per_second > 0.0 {
+            Duration::from_secs_f64(1.0 / requests_per_second)
+        } else {
+            Duration::ZERO
+        };
+
+        Self {
+            interval,
+            hosts: Default::default(),
+        }
+    }
+
+    /// The time between the requests to a host.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Wait until a request to the host of the url is allowed.
+    pub async fn acquire(&self, url: &str) {
+        if self.interval.is_zero() {
+            return;
+        }
+
+        let host = crate::utils::get_domain_from_url(url);
+        let now = Instant::now();
+
+        let slot = match self.hosts.lock() {
+            Ok(mut hosts) => {
+                let slot = match hosts.get(host) {
+                    Some(next) if *next > now => *next,
+                    _ => now,
+                };
+                if hosts.len() >= IDLE_HOSTS_LIMIT && !hosts.contains_key(host) {
+                    hosts.retain(|_, next| *next > now);
+                }
+                hosts.insert(host.into(), slot + self.interval);
+                slot
+            }
+            _ => now,
+        };
+
+        if slot > now {
+            tokio::time::sleep_until(slot).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter() {
+        let limiter = RateLimiter::new(20.0);
+        let shared = limiter.clone();
+        let start = Instant::now();
+
+        limiter.acquire("https://example.com/a").await;
+        shared.acquire("https://example.com/b").await;
+        limiter.acquire("https://other.com/").await;
+
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        shared.acquire("https://example.com/c").await;
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_evicts_idle_hosts() {
+        let limiter = RateLimiter::new(20.0);
+
+        for i in 0..IDLE_HOSTS_LIMIT {
+            limiter.acquire(&format!("https://{i}.example.com/")).await;
+        }
+
+        tokio::time::advance(Duration::from_millis(100)).await;
+        limiter.acquire("https://example.com/").await;
+
+        assert_eq!(limiter.hosts.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::utils::url_store::UrlStore;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The unix seconds of the time to keep in the url store.
+pub fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Record the url as crawled now when the page was fetched successfully, the failed fetches are retried on the next crawl.
+pub fn record_fetch(store: &dyn UrlStore, url: &str, status_code: crate::client::StatusCode) {
+    if status_code.is_success() {
+        store.set(url, unix_secs(SystemTime::now()));
+    }
+}
+
+/// Record the url of the page rendered as crawled when the page was fetched successfully.
+#[cfg(feature = "chrome")]
+pub(crate) fn record_page(store: &Option<std::sync::Arc<dyn UrlStore>>, page: &crate::page::Page) {
+    if let Some(store) = store {
+        record_fetch(store.as_ref(), page.get_url(), page.status_code);
+    }
+}
+
+/// The url was crawled within the freshness window. The store holds the unix seconds the urls were last crawled.
+pub fn is_fresh(store: &dyn UrlStore, url: &str, recrawl_after: Duration, now: SystemTime) -> bool {
+    match store.get(url) {
+        Some(secs) => now
+            .duration_since(UNIX_EPOCH + Duration::from_secs(secs))
+            .map_or(true, |elapsed| elapsed < recrawl_after),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::url_store::MemoryUrlStore;
+
+    #[test]
+    fn test_is_fresh() {
+        let store = MemoryUrlStore::new();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(60 * 60 * 24);
+
+        store.set(
+            "https://example.com/a",
+            unix_secs(now - Duration::from_secs(60 * 60)),
+        );
+        store.set("https://example.com/b", unix_secs(now - day));
+
+        assert!(is_fresh(&store, "https://example.com/a", day, now));
+        assert!(!is_fresh(&store, "https://example.com/b", day, now));
+        assert!(!is_fresh(&store, "https://example.com/c", day, now));
+    }
+
+    #[test]
+    fn test_record_fetch() {
+        let store = MemoryUrlStore::new();
+        let day = Duration::from_secs(60 * 60 * 24);
+
+        record_fetch(
+            &store,
+            "https://example.com/a",
+            crate::client::StatusCode::OK,
+        );
+        record_fetch(
+            &store,
+            "https://example.com/b",
+            crate::client::StatusCode::SERVICE_UNAVAILABLE,
+        );
+
+        assert!(is_fresh(
+            &store,
+            "https://example.com/a",
+            day,
+            SystemTime::now()
+        ));
+        assert_eq!(store.get("https://example.com/b"), None);
+    }
+}
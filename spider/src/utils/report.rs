@@ -0,0 +1,115 @@
+use crate::page::Page;
+use hashbrown::HashMap;
+use std::time::Duration;
+
+/// The summary of a crawl.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrawlReport {
+    /// The amount of pages crawled.
+    pub total_pages: usize,
+    /// The amount of pages by status code.
+    pub status_codes: HashMap<u16, usize>,
+    /// The urls that failed with the reason of the failure.
+    pub failed: Vec<(String, String)>,
+    /// The bytes transferred for the pages. The size of the html is used when the bytes transferred are unknown.
+    pub total_bytes: usize,
+    /// The time the crawl took.
+    pub duration: Duration,
+    /// The average time of the requests. This is always `None` without the `time` flag enabled.
+    pub average_latency: Option<Duration>,
+    /// The total time of the requests for the average.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    latency_total: Duration,
+    /// The amount of requests timed for the average.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    latency_count: u32,
+}
+
+impl CrawlReport {
+    /// A new empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the page to the report.
+    pub fn record(&mut self, page: &Page) {
+        let status = page.status_code;
+
+        self.total_pages += 1;
+        *self.status_codes.entry(status.as_u16()).or_default() += 1;
+
+        if let Some(error) = &page.error_status {
+            self.failed.push((page.get_url().into(), error.to_string()));
+        } else if status.is_client_error() || status.is_server_error() {
+            self.failed.push((
+                page.get_url().into(),
+                status.canonical_reason().unwrap_or(status.as_str()).into(),
+            ));
+        }
+
+        #[cfg(not(feature = "decentralized"))]
+        let bytes = page.bytes_transferred.map(|bytes| bytes as usize);
+        #[cfg(feature = "decentralized")]
+        let bytes: Option<usize> = None;
+
+        self.total_bytes += bytes.unwrap_or_else(|| page.html_len());
+
+        if let Some(latency) = page.latency() {
+            self.latency_total += latency;
+            self.latency_count += 1;
+            self.average_latency = Some(self.latency_total / self.latency_count);
+        }
+    }
+
+    /// The amount of pages that failed.
+    pub fn failed_pages(&self) -> usize {
+        self.failed.len()
+    }
+}
+
+impl<'a> FromIterator<&'a Page> for CrawlReport {
+    fn from_iter<I: IntoIterator<Item = &'a Page>>(pages: I) -> Self {
+        let mut report = Self::new();
+
+        for page in pages {
+            report.record(page);
+        }
+
+        report
+    }
+}
+
+#[cfg(all(test, not(feature = "decentralized")))]
+mod tests {
+    use super::*;
+    use crate::utils::PageResponse;
+
+    #[test]
+    fn test_crawl_report() {
+        let ok = crate::page::build(
+            "https://example.com/",
+            PageResponse {
+                content: Some(Box::new(b"<html>ok</html>".to_vec())),
+                status_code: reqwest::StatusCode::OK,
+                ..Default::default()
+            },
+        );
+        let missing = crate::page::build(
+            "https://example.com/missing",
+            PageResponse {
+                status_code: reqwest::StatusCode::NOT_FOUND,
+                ..Default::default()
+            },
+        );
+
+        let report: CrawlReport = [&ok, &missing].into_iter().collect();
+
+        assert_eq!(report.total_pages, 2);
+        assert_eq!(report.status_codes.get(&200), Some(&1));
+        assert_eq!(report.status_codes.get(&404), Some(&1));
+        assert_eq!(report.failed_pages(), 1);
+        assert_eq!(report.failed[0].0, "https://example.com/missing");
+        assert_eq!(report.total_bytes, 15);
+    }
+}
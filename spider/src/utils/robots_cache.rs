@@ -0,0 +1,137 @@
+use crate::packages::robotparser::parser::RobotFileParser;
+use hashbrown::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The counters of the robots.txt cache.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RobotsCacheStats {
+    /// The lookups served from the cache.
+    pub hits: usize,
+    /// The lookups of the origins not cached.
+    pub misses: usize,
+    /// The lookups of the origins cached longer than the ttl.
+    pub expired: usize,
+    /// The amount of origins cached.
+    pub entries: usize,
+}
+
+/// The parsed robots.txt files with the time they were cached by origin.
+type CachedOrigins = Arc<Mutex<HashMap<String, (Box<RobotFileParser>, Instant)>>>;
+
+/// The parsed robots.txt files by origin url, ex: `https://example.com`, shared across crawls. Clones share the same cache so one cache can be passed to multiple websites.
+#[derive(Debug, Clone, Default)]
+pub struct RobotsCache {
+    /// The time to keep the robots.txt files before fetching them again. The files are kept for the lifetime of the cache when `None`.
+    ttl: Option<Duration>,
+    /// The parsed robots.txt files with the time they were cached by origin.
+    origins: CachedOrigins,
+    /// The lookups served from the cache.
+    hits: Arc<AtomicUsize>,
+    /// The lookups of the origins not cached.
+    misses: Arc<AtomicUsize>,
+    /// The lookups of the origins cached longer than the ttl.
+    expired: Arc<AtomicUsize>,
+}
+
+impl RobotsCache {
+    /// A new cache keeping the robots.txt files for the ttl.
+    pub fn new(ttl: Option<Duration>) -> Self {
+        Self {
+            ttl,
+            ..Default::default()
+        }
+    }
+
+    /// The time to keep the robots.txt files before fetching them again.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// The parsed robots.txt of the origin url when cached within the ttl.
+    pub fn get(&self, origin: &str) -> Option<Box<RobotFileParser>> {
+        let mut origins = self.origins.lock().ok()?;
+
+        let expired = match origins.get(origin) {
+            Some((_, cached_at)) => self.ttl.is_some_and(|ttl| cached_at.elapsed() >= ttl),
+            _ => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        };
+
+        if expired {
+            origins.remove(origin);
+            self.expired.fetch_add(1, Ordering::Relaxed);
+            None
+        } else {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            origins.get(origin).map(|(parser, _)| parser.clone())
+        }
+    }
+
+    /// Cache the parsed robots.txt of the origin url.
+    pub fn insert(&self, origin: &str, parser: Box<RobotFileParser>) {
+        if let Ok(mut origins) = self.origins.lock() {
+            origins.insert(origin.into(), (parser, Instant::now()));
+        }
+    }
+
+    /// Remove all of the robots.txt files cached.
+    pub fn clear(&self) {
+        if let Ok(mut origins) = self.origins.lock() {
+            origins.clear();
+        }
+    }
+
+    /// The counters of the cache.
+    pub fn stats(&self) -> RobotsCacheStats {
+        RobotsCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+            entries: self
+                .origins
+                .lock()
+                .map(|origins| origins.len())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_cache() {
+        let cache = RobotsCache::new(Some(Duration::ZERO));
+        let mut parser = RobotFileParser::new();
+        parser.read_str("User-agent: *\nDisallow: /private");
+
+        assert!(cache.get("https://example.com").is_none());
+
+        cache.insert("https://example.com", parser.clone());
+
+        assert!(cache.get("https://example.com").is_none());
+        assert_eq!(
+            cache.stats(),
+            RobotsCacheStats {
+                hits: 0,
+                misses: 1,
+                expired: 1,
+                entries: 0,
+            }
+        );
+
+        let cache = RobotsCache::new(None);
+        cache.insert("https://example.com", parser);
+
+        let cached = cache.clone().get("https://example.com").expect("cached");
+
+        assert!(!cached.can_fetch("*", "https://example.com/private"));
+        assert_eq!(cache.stats().hits, 1);
+    }
+}
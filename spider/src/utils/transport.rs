@@ -0,0 +1,195 @@
+use crate::client::header::HeaderMap;
+use crate::client::StatusCode;
+use hashbrown::HashMap;
+
+/// A canned response served by a transport.
+#[derive(Debug, Clone, Default)]
+pub struct TransportResponse {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    /// A new response with the status and body.
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Default::default(),
+            body: body.into(),
+        }
+    }
+
+    /// A new `200` html response.
+    pub fn html(body: impl Into<Vec<u8>>) -> Self {
+        let mut response = Self::new(StatusCode::OK, body);
+        response.headers.insert(
+            crate::client::header::CONTENT_TYPE,
+            crate::client::header::HeaderValue::from_static("text/html; charset=utf-8"),
+        );
+        response
+    }
+}
+
+/// Serve the responses of the HTTP request without the network, ex: canned responses for tests.
+pub trait Transport: Send + Sync {
+    /// The response for the url. Return `None` to make the request over the network.
+    fn fetch(&self, url: &str) -> Option<TransportResponse>;
+}
+
+/// A transport serving canned responses from a map of url to response.
+#[derive(Debug, Clone)]
+pub struct MockTransport {
+    /// The responses by url.
+    responses: HashMap<String, TransportResponse>,
+    /// Serve a `404` for the urls not found instead of using the network.
+    strict: bool,
+}
+
+impl MockTransport {
+    /// A new mock transport that serves a `404` for the urls not found.
+    pub fn new() -> Self {
+        Self {
+            responses: Default::default(),
+            strict: true,
+        }
+    }
+
+    /// Use the network for the urls not found instead of serving a `404`.
+    pub fn with_network_fallback(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+
+    /// Serve the response for the url.
+    pub fn with_response(mut self, url: &str, response: TransportResponse) -> Self {
+        self.responses.insert(url.into(), response);
+        self
+    }
+
+    /// Serve the html for the url.
+    pub fn with_html(self, url: &str, html: &str) -> Self {
+        self.with_response(url, TransportResponse::html(html))
+    }
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for MockTransport {
+    fn fetch(&self, url: &str) -> Option<TransportResponse> {
+        match self.responses.get(url) {
+            Some(response) => Some(response.clone()),
+            _ if self.strict => Some(TransportResponse::new(StatusCode::NOT_FOUND, "")),
+            _ => None,
+        }
+    }
+}
+
+/// Read the response of a `file://` url from the filesystem. A directory serves its `index.html`.
+pub fn file_response(url: &str) -> Option<TransportResponse> {
+    let path = url::Url::parse(url).ok()?.to_file_path().ok()?;
+    let path = if path.is_dir() {
+        path.join("index.html")
+    } else {
+        path
+    };
+
+    Some(match std::fs::read(&path) {
+        Ok(body) => match path.extension().and_then(|ext| ext.to_str()) {
+            Some("html" | "htm") => TransportResponse::html(body),
+            _ => TransportResponse::new(StatusCode::OK, body),
+        },
+        _ => TransportResponse::new(StatusCode::NOT_FOUND, ""),
+    })
+}
+
+/// Get the response of the url from the transport as a client response. The `file://` urls are read from the filesystem.
+#[cfg(not(feature = "wreq"))]
+pub(crate) fn transport_response(
+    transport: &Option<std::sync::Arc<dyn Transport>>,
+    url: &str,
+) -> Option<reqwest::Response> {
+    use reqwest::ResponseBuilderExt;
+
+    let response = match transport
+        .as_ref()
+        .and_then(|transport| transport.fetch(url))
+    {
+        Some(response) => response,
+        _ if url.starts_with("file://") => file_response(url)?,
+        _ => return None,
+    };
+    let mut builder = http::Response::builder()
+        .status(response.status)
+        .url(url::Url::parse(url).ok()?);
+
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers;
+    }
+
+    builder
+        .body(response.body)
+        .ok()
+        .map(reqwest::Response::from)
+}
+
+/// Get the response of the url from the transport as a client response.
+#[cfg(feature = "wreq")]
+pub(crate) fn transport_response(
+    _transport: &Option<std::sync::Arc<dyn Transport>>,
+    _url: &str,
+) -> Option<crate::client::Response> {
+    None
+}
+
+#[cfg(all(test, not(feature = "wreq")))]
+mod tests {
+    use super::{transport_response, MockTransport, Transport};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_mock_transport() {
+        let transport: Option<Arc<dyn Transport>> = Some(Arc::new(
+            MockTransport::new().with_html("https://example.com/", "<a href=\"/a\">A</a>"),
+        ));
+
+        let res = transport_response(&transport, "https://example.com/").unwrap();
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.url().as_str(), "https://example.com/");
+        assert_eq!(res.text().await.unwrap(), "<a href=\"/a\">A</a>");
+
+        let res = transport_response(&transport, "https://example.com/missing").unwrap();
+
+        assert_eq!(res.status(), 404);
+        assert!(transport_response(&None, "https://example.com/").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_transport() {
+        let dir =
+            std::env::temp_dir().join(format!("spider_test_file_transport_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("index.html"), "<a href=\"about.html\">About</a>").unwrap();
+
+        let url = url::Url::from_directory_path(&dir).unwrap();
+        let res = transport_response(&None, url.as_str()).unwrap();
+        let missing =
+            transport_response(&None, url.join("missing.html").unwrap().as_str()).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(
+            res.text().await.unwrap(),
+            "<a href=\"about.html\">About</a>"
+        );
+        assert_eq!(missing.status(), 404);
+    }
+}
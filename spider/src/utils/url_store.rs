@@ -0,0 +1,255 @@
+use hashbrown::HashMap;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Store a value by url kept across crawls, ex: the time the urls were crawled for the recrawls or the content hash of the pages for the diff crawls.
+pub trait UrlStore: Send + Sync {
+    /// The value of the url.
+    fn get(&self, url: &str) -> Option<u64>;
+    /// Record the value of the url.
+    fn set(&self, url: &str, value: u64);
+    /// Remove the url from the store.
+    fn remove(&self, url: &str);
+    /// All of the urls of the store.
+    fn urls(&self) -> Vec<String>;
+    /// Persist the pending writes of the store.
+    fn flush(&self) {}
+}
+
+/// A url store kept in memory for the lifetime of the website.
+#[derive(Debug, Default)]
+pub struct MemoryUrlStore {
+    /// The value by url.
+    values: Mutex<HashMap<String, u64>>,
+}
+
+impl MemoryUrlStore {
+    /// A new empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The amount of urls in the store.
+    fn len(&self) -> usize {
+        match self.values.lock() {
+            Ok(values) => values.len(),
+            _ => 0,
+        }
+    }
+}
+
+impl UrlStore for MemoryUrlStore {
+    fn get(&self, url: &str) -> Option<u64> {
+        match self.values.lock() {
+            Ok(values) => values.get(url).copied(),
+            _ => None,
+        }
+    }
+
+    fn set(&self, url: &str, value: u64) {
+        if let Ok(mut values) = self.values.lock() {
+            values.insert(url.into(), value);
+        }
+    }
+
+    fn remove(&self, url: &str) {
+        if let Ok(mut values) = self.values.lock() {
+            values.remove(url);
+        }
+    }
+
+    fn urls(&self) -> Vec<String> {
+        match self.values.lock() {
+            Ok(values) => values.keys().cloned().collect(),
+            _ => Default::default(),
+        }
+    }
+}
+
+/// A url store persisted to a file to keep the values across runs, ex: scheduled recrawls or change monitoring. Each change is appended as a line of the value and the url separated by a tab, an empty value removes the url. The writes are buffered until the store is flushed or dropped, and the stale lines are dropped when the store is opened.
+#[derive(Debug)]
+pub struct FileUrlStore {
+    /// The path of the file.
+    path: PathBuf,
+    /// The values loaded from the file.
+    memory: MemoryUrlStore,
+    /// The buffered writer of the file opened on the first write.
+    writer: Mutex<Option<BufWriter<std::fs::File>>>,
+}
+
+impl FileUrlStore {
+    /// Open the store at the path loading the values of the previous runs. The file is rewritten with only the current values when it holds stale lines, and created on the first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let memory = MemoryUrlStore::new();
+        let mut lines = 0;
+
+        if let Ok(file) = std::fs::File::open(&path) {
+            for line in std::io::BufReader::new(file).lines().map_while(Result::ok) {
+                lines += 1;
+
+                if let Some((value, url)) = line.split_once('\t') {
+                    match value.parse::<u64>() {
+                        Ok(value) => memory.set(url, value),
+                        _ if value.is_empty() => memory.remove(url),
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        let store = Self {
+            path,
+            memory,
+            writer: Mutex::new(None),
+        };
+
+        if lines > store.memory.len() {
+            store.compact();
+        }
+
+        store
+    }
+
+    /// Rewrite the file with the current values dropping the stale lines.
+    fn compact(&self) {
+        let tmp = self.path.with_extension("compact");
+
+        let written = std::fs::File::create(&tmp).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+
+            if let Ok(values) = self.memory.values.lock() {
+                for (url, value) in values.iter() {
+                    writeln!(writer, "{value}\t{url}")?;
+                }
+            }
+
+            writer.flush()
+        });
+
+        match written.and_then(|_| std::fs::rename(&tmp, &self.path)) {
+            Ok(_) => (),
+            Err(e) => {
+                log::warn!("failed to compact the url store: {e}");
+                let _ = std::fs::remove_file(&tmp);
+            }
+        }
+    }
+
+    /// Append the line to the file.
+    fn append(&self, line: std::fmt::Arguments) {
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+
+        if writer.is_none() {
+            match std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+            {
+                Ok(file) => *writer = Some(BufWriter::new(file)),
+                Err(e) => {
+                    log::warn!("failed to open the url store: {e}");
+                    return;
+                }
+            }
+        }
+
+        if let Some(writer) = writer.as_mut() {
+            if let Err(e) = writeln!(writer, "{line}") {
+                log::warn!("failed to write the url store: {e}");
+            }
+        }
+    }
+}
+
+impl UrlStore for FileUrlStore {
+    fn get(&self, url: &str) -> Option<u64> {
+        self.memory.get(url)
+    }
+
+    fn set(&self, url: &str, value: u64) {
+        if self.memory.get(url) != Some(value) {
+            self.memory.set(url, value);
+            self.append(format_args!("{value}\t{url}"));
+        }
+    }
+
+    fn remove(&self, url: &str) {
+        if self.memory.get(url).is_some() {
+            self.memory.remove(url);
+            self.append(format_args!("\t{url}"));
+        }
+    }
+
+    fn urls(&self) -> Vec<String> {
+        self.memory.urls()
+    }
+
+    fn flush(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            if let Some(writer) = writer.as_mut() {
+                if let Err(e) = writer.flush() {
+                    log::warn!("failed to flush the url store: {e}");
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FileUrlStore {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_url_store() {
+        let path =
+            std::env::temp_dir().join(format!("spider_url_store_{}.tsv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let store = FileUrlStore::new(&path);
+            store.set("https://example.com/", 1);
+            store.set("https://example.com/a", 2);
+            store.remove("https://example.com/a");
+        }
+
+        let store = FileUrlStore::new(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(store.get("https://example.com/"), Some(1));
+        assert_eq!(store.get("https://example.com/a"), None);
+        assert_eq!(store.urls(), vec!["https://example.com/"]);
+    }
+
+    #[test]
+    fn test_file_url_store_compact() {
+        let path = std::env::temp_dir().join(format!(
+            "spider_url_store_compact_{}.tsv",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        for value in 1..=3 {
+            let store = FileUrlStore::new(&path);
+            store.set("https://example.com/", value);
+            store.set("https://example.com/a", value);
+            store.remove("https://example.com/a");
+        }
+
+        let store = FileUrlStore::new(&path);
+        let lines = std::fs::read_to_string(&path).unwrap_or_default();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(lines, "3\thttps://example.com/\n");
+        assert_eq!(store.get("https://example.com/"), Some(3));
+    }
+}
@@ -0,0 +1,881 @@
+use crate::client::header::{HeaderMap, HeaderName, HeaderValue};
+use crate::client::StatusCode;
+use crate::utils::transport::{MockTransport, TransportResponse};
+use hashbrown::HashMap;
+use std::io::{BufRead, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+/// The max length of the content block read for a record. The blocks past the limit are skipped and the record is read without content.
+pub const MAX_WARC_RECORD_LENGTH: u64 = 64 * 1024 * 1024;
+
+/// A record of a WARC archive.
+#[derive(Debug, Clone, Default)]
+pub struct WarcRecord {
+    /// The WARC version of the record, ex: `WARC/1.1`.
+    pub version: String,
+    /// The named fields of the record header.
+    pub headers: Vec<(String, String)>,
+    /// The content block of the record.
+    pub content: Vec<u8>,
+}
+
+impl WarcRecord {
+    /// The value of the header field. The name is case-insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The type of the record, ex: `response`, `request`, or `warcinfo`.
+    pub fn warc_type(&self) -> Option<&str> {
+        self.header("WARC-Type")
+    }
+
+    /// The url the record was captured from.
+    pub fn target_uri(&self) -> Option<&str> {
+        self.header("WARC-Target-URI")
+            .map(|uri| uri.trim_start_matches('<').trim_end_matches('>'))
+    }
+
+    /// The archived HTTP response of a `response` record. The chunked and compressed bodies are decoded.
+    pub fn http_response(&self) -> Option<TransportResponse> {
+        if self.warc_type() != Some("response") {
+            return None;
+        }
+
+        let (head, body) = split_http_message(&self.content)?;
+        let head = String::from_utf8_lossy(head);
+        let mut lines = head.lines();
+        let status = lines
+            .next()?
+            .split_whitespace()
+            .nth(1)
+            .and_then(|code| StatusCode::from_bytes(code.as_bytes()).ok())?;
+
+        let mut headers = HeaderMap::new();
+
+        for line in lines {
+            if let Some((name, value)) = line.split_once(':') {
+                if let (Ok(name), Ok(value)) = (
+                    HeaderName::from_bytes(name.trim().as_bytes()),
+                    HeaderValue::from_str(value.trim()),
+                ) {
+                    headers.append(name, value);
+                }
+            }
+        }
+
+        let mut body = body.to_vec();
+
+        if header_contains(&headers, "transfer-encoding", "chunked") {
+            body = decode_chunked(&body);
+            headers.remove("transfer-encoding");
+        }
+
+        let decoded = if header_contains(&headers, "content-encoding", "gzip") {
+            read_all(flate2::read::MultiGzDecoder::new(&body[..]))
+        } else if header_contains(&headers, "content-encoding", "deflate") {
+            read_all(flate2::read::ZlibDecoder::new(&body[..]))
+        } else {
+            None
+        };
+
+        if let Some(decoded) = decoded {
+            body = decoded;
+            headers.remove("content-encoding");
+            headers.remove("content-length");
+        }
+
+        Some(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Read the records of a WARC archive.
+pub struct WarcReader<R> {
+    /// The archive reader.
+    reader: R,
+    /// The bytes read of the uncompressed archive.
+    position: u64,
+    /// The start of the last record read in the uncompressed archive.
+    record_start: u64,
+}
+
+impl<R: BufRead> WarcReader<R> {
+    /// A new reader of the uncompressed WARC archive.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            position: 0,
+            record_start: 0,
+        }
+    }
+
+    /// Read a line counting the bytes read.
+    fn read_line(&mut self, line: &mut Vec<u8>) -> std::io::Result<usize> {
+        let read = self.reader.read_until(b'\n', line)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+
+    /// Read the next record. Returns `None` at the end of the archive.
+    pub fn read_record(&mut self) -> std::io::Result<Option<WarcRecord>> {
+        let mut line = Vec::new();
+
+        let version = loop {
+            line.clear();
+
+            let start = self.position;
+
+            if self.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+
+            let version = String::from_utf8_lossy(&line).trim().to_string();
+
+            if !version.is_empty() {
+                self.record_start = start;
+                break version;
+            }
+        };
+
+        if !version.starts_with("WARC/") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid WARC record version line",
+            ));
+        }
+
+        let mut record = WarcRecord {
+            version,
+            ..Default::default()
+        };
+
+        loop {
+            line.clear();
+
+            if self.read_line(&mut line)? == 0 {
+                break;
+            }
+
+            let field = String::from_utf8_lossy(&line);
+            let field = field.trim_end();
+
+            if field.is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = field.split_once(':') {
+                record
+                    .headers
+                    .push((name.trim().to_string(), value.trim().to_string()));
+            }
+        }
+
+        let length = record
+            .header("Content-Length")
+            .and_then(|length| length.parse::<u64>().ok())
+            .unwrap_or_default();
+
+        let mut block = (&mut self.reader).take(length);
+
+        let read = if length > MAX_WARC_RECORD_LENGTH {
+            log::warn!("skipping the WARC record content of {length} bytes past the limit");
+            std::io::copy(&mut block, &mut std::io::sink())?
+        } else {
+            block.read_to_end(&mut record.content)? as u64
+        };
+
+        self.position += read;
+
+        if read < length {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "the WARC record content is shorter than the Content-Length",
+            ));
+        }
+
+        Ok(Some(record))
+    }
+}
+
+impl WarcReader<Box<dyn BufRead>> {
+    /// Open the WARC archive at the path. The `.gz` archives are decompressed.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        let reader: Box<dyn BufRead> = if path.extension().is_some_and(|ext| ext == "gz") {
+            Box::new(std::io::BufReader::new(flate2::read::MultiGzDecoder::new(
+                file,
+            )))
+        } else {
+            Box::new(std::io::BufReader::new(file))
+        };
+
+        Ok(Self::new(reader))
+    }
+}
+
+impl<R: BufRead> Iterator for WarcReader<R> {
+    type Item = std::io::Result<WarcRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+impl MockTransport {
+    /// A new mock transport serving the archived responses of the WARC archive at the path. All of the responses are kept in memory, use a [`WarcTransport`] for the large archives.
+    pub fn from_warc(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut transport = MockTransport::new();
+
+        for record in WarcReader::open(path)? {
+            let record = record?;
+
+            if let (Some(url), Some(response)) = (record.target_uri(), record.http_response()) {
+                transport = transport.with_response(url, response);
+            }
+        }
+
+        Ok(transport)
+    }
+}
+
+/// A transport serving the archived responses of a WARC archive. Only the offsets of the records are kept in memory and the record is read from the archive on each request. A `404` is served for the urls not archived.
+#[derive(Debug, Clone)]
+pub struct WarcTransport {
+    /// The path of the archive.
+    path: PathBuf,
+    /// The offset of the last `response` record in the uncompressed archive by url.
+    offsets: HashMap<String, u64>,
+    /// The url of the first archived html page.
+    first_html: Option<String>,
+}
+
+impl WarcTransport {
+    /// Index the responses of the WARC archive at the path. The `.gz` archives are decompressed up to the record on each request.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut reader = WarcReader::open(&path)?;
+        let mut offsets = HashMap::new();
+        let mut first_html = None;
+
+        while let Some(record) = reader.read_record()? {
+            if let (Some(url), Some(response)) = (record.target_uri(), record.http_response()) {
+                if first_html.is_none()
+                    && header_contains(&response.headers, "content-type", "html")
+                {
+                    first_html = Some(url.to_string());
+                }
+                offsets.insert(url.to_string(), reader.record_start);
+            }
+        }
+
+        Ok(Self {
+            path,
+            offsets,
+            first_html,
+        })
+    }
+
+    /// The url of the first archived html page.
+    pub fn first_html(&self) -> Option<&str> {
+        self.first_html.as_deref()
+    }
+
+    /// Read the record at the offset of the uncompressed archive.
+    fn read_at(&self, offset: u64) -> std::io::Result<Option<WarcRecord>> {
+        let mut file = std::fs::File::open(&self.path)?;
+
+        if self.path.extension().is_some_and(|ext| ext == "gz") {
+            let mut reader = std::io::BufReader::new(flate2::read::MultiGzDecoder::new(file));
+            std::io::copy(&mut (&mut reader).take(offset), &mut std::io::sink())?;
+            WarcReader::new(reader).read_record()
+        } else {
+            file.seek(std::io::SeekFrom::Start(offset))?;
+            WarcReader::new(std::io::BufReader::new(file)).read_record()
+        }
+    }
+}
+
+impl crate::utils::transport::Transport for WarcTransport {
+    fn fetch(&self, url: &str) -> Option<TransportResponse> {
+        let response = self
+            .offsets
+            .get(url)
+            .and_then(|offset| match self.read_at(*offset) {
+                Ok(record) => record,
+                Err(err) => {
+                    log::warn!("failed to read the WARC record of {url}: {err}");
+                    None
+                }
+            })
+            .and_then(|record| record.http_response());
+
+        Some(response.unwrap_or_else(|| TransportResponse::new(StatusCode::NOT_FOUND, "")))
+    }
+}
+
+/// A message to the writer task of the archive.
+#[derive(Debug)]
+enum WarcMessage {
+    /// The records to append.
+    Records(Vec<u8>),
+    /// Flush the archive and notify.
+    Flush(tokio::sync::oneshot::Sender<()>),
+}
+
+/// Write the requests and responses of a crawl to a WARC archive. The records are sent to a blocking task writing the archive with a buffer.
+#[derive(Debug)]
+pub struct WarcWriter {
+    /// The records sent to the writer task.
+    sender: std::sync::mpsc::Sender<WarcMessage>,
+    /// The headers the client adds to every request, ex: the user agent.
+    request_headers: HeaderMap,
+}
+
+impl WarcWriter {
+    /// Open the WARC archive at the path to append the records. A `warcinfo` record starts a new archive. The writer needs the tokio runtime.
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        let empty = file.metadata()?.len() == 0;
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        runtime.spawn_blocking(move || write_archive(file, receiver));
+
+        let writer = Self {
+            sender,
+            request_headers: HeaderMap::new(),
+        };
+
+        if empty {
+            writer.write_record(
+                "warcinfo",
+                None,
+                None,
+                "application/warc-fields",
+                concat!(
+                    "software: spider/",
+                    env!("CARGO_PKG_VERSION"),
+                    "\r\nformat: WARC File Format 1.1\r\n"
+                )
+                .as_bytes(),
+            )?;
+        }
+
+        Ok(writer)
+    }
+
+    /// Set the headers the client adds to every request to write with the `request` records.
+    pub fn with_request_headers(mut self, headers: HeaderMap) -> Self {
+        self.request_headers = headers;
+        self
+    }
+
+    /// Send the records to the writer task.
+    fn send(&self, records: Vec<u8>) -> std::io::Result<()> {
+        self.sender
+            .send(WarcMessage::Records(records))
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+    }
+
+    /// Write a record returning the record id.
+    pub fn write_record(
+        &self,
+        warc_type: &str,
+        target_uri: Option<&str>,
+        concurrent_to: Option<&str>,
+        content_type: &str,
+        block: &[u8],
+    ) -> std::io::Result<String> {
+        let (id, record) = record(warc_type, target_uri, concurrent_to, content_type, block);
+
+        self.send(record)?;
+
+        Ok(id)
+    }
+
+    /// Write the `response` record of the fetched resource and the `request` record that fetched it. The body is stored decoded.
+    /// The method, headers, and body of the request are written from the custom request, a `GET` is written without one.
+    pub fn write_exchange(
+        &self,
+        url: &str,
+        status: StatusCode,
+        version: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+        request: Option<&crate::page::CrawlRequest>,
+    ) -> std::io::Result<()> {
+        let mut response = string_concat!(
+            version,
+            " ",
+            status.as_str(),
+            " ",
+            status.canonical_reason().unwrap_or_default(),
+            "\r\n"
+        )
+        .into_bytes();
+
+        for (name, value) in headers {
+            if name == "content-encoding" || name == "transfer-encoding" || name == "content-length"
+            {
+                continue;
+            }
+            push_header(&mut response, name.as_str(), value);
+        }
+
+        response.extend_from_slice(
+            string_concat!("content-length: ", body.len().to_string(), "\r\n\r\n").as_bytes(),
+        );
+        response.extend_from_slice(body);
+
+        let (id, mut records) = record(
+            "response",
+            Some(url),
+            None,
+            "application/http;msgtype=response",
+            &response,
+        );
+
+        let method = request.map_or("GET", |request| request.method.as_str());
+        let request_body = request
+            .and_then(|request| request.body.as_deref())
+            .unwrap_or_default();
+
+        let mut http_request = match url::Url::parse(url) {
+            Ok(u) => string_concat!(
+                method,
+                " ",
+                &u[url::Position::BeforePath..url::Position::AfterQuery],
+                " ",
+                version,
+                "\r\nhost: ",
+                &u[url::Position::BeforeHost..url::Position::AfterPort],
+                "\r\n"
+            ),
+            _ => string_concat!(method, " ", url, " ", version, "\r\n"),
+        }
+        .into_bytes();
+
+        let request_headers = request.and_then(|request| request.headers.as_ref());
+
+        for (name, value) in &self.request_headers {
+            if !request_headers.is_some_and(|headers| headers.contains_key(name)) {
+                push_header(&mut http_request, name.as_str(), value);
+            }
+        }
+
+        for (name, value) in request_headers.into_iter().flatten() {
+            push_header(&mut http_request, name.as_str(), value);
+        }
+
+        if !request_body.is_empty() {
+            http_request.extend_from_slice(
+                string_concat!("content-length: ", request_body.len().to_string(), "\r\n")
+                    .as_bytes(),
+            );
+        }
+
+        http_request.extend_from_slice(b"\r\n");
+        http_request.extend_from_slice(request_body);
+
+        let (_, request_record) = record(
+            "request",
+            Some(url),
+            Some(&id),
+            "application/http;msgtype=request",
+            &http_request,
+        );
+
+        records.extend_from_slice(&request_record);
+
+        self.send(records)
+    }
+
+    /// Flush the records sent to the archive.
+    pub async fn flush(&self) {
+        let (done, flushed) = tokio::sync::oneshot::channel();
+
+        if self.sender.send(WarcMessage::Flush(done)).is_ok() {
+            let _ = flushed.await;
+        }
+    }
+}
+
+/// Write the records received to the archive until the writer is dropped.
+fn write_archive(file: std::fs::File, receiver: std::sync::mpsc::Receiver<WarcMessage>) {
+    let mut file = std::io::BufWriter::new(file);
+
+    while let Ok(message) = receiver.recv() {
+        match message {
+            WarcMessage::Records(records) => {
+                if let Err(err) = file.write_all(&records) {
+                    log::warn!("failed to write the WARC records: {err}");
+                }
+            }
+            WarcMessage::Flush(done) => {
+                if let Err(err) = file.flush() {
+                    log::warn!("failed to flush the WARC archive: {err}");
+                }
+                let _ = done.send(());
+            }
+        }
+    }
+
+    if let Err(err) = file.flush() {
+        log::warn!("failed to flush the WARC archive: {err}");
+    }
+}
+
+/// A new record returning the record id and the bytes of the record.
+fn record(
+    warc_type: &str,
+    target_uri: Option<&str>,
+    concurrent_to: Option<&str>,
+    content_type: &str,
+    block: &[u8],
+) -> (String, Vec<u8>) {
+    let id = record_id();
+    let mut head = string_concat!(
+        "WARC/1.1\r\nWARC-Type: ",
+        warc_type,
+        "\r\nWARC-Record-ID: ",
+        id,
+        "\r\nWARC-Date: ",
+        warc_date(std::time::SystemTime::now()),
+        "\r\n"
+    );
+
+    if let Some(target_uri) = target_uri {
+        head.push_str(&string_concat!("WARC-Target-URI: ", target_uri, "\r\n"));
+    }
+
+    if let Some(concurrent_to) = concurrent_to {
+        head.push_str(&string_concat!(
+            "WARC-Concurrent-To: ",
+            concurrent_to,
+            "\r\n"
+        ));
+    }
+
+    head.push_str(&string_concat!(
+        "Content-Type: ",
+        content_type,
+        "\r\nContent-Length: ",
+        block.len().to_string(),
+        "\r\n\r\n"
+    ));
+
+    let mut record = head.into_bytes();
+
+    record.extend_from_slice(block);
+    record.extend_from_slice(b"\r\n\r\n");
+
+    (id, record)
+}
+
+/// Push the header line to the HTTP message.
+fn push_header(message: &mut Vec<u8>, name: &str, value: &HeaderValue) {
+    message.extend_from_slice(name.as_bytes());
+    message.extend_from_slice(b": ");
+    message.extend_from_slice(value.as_bytes());
+    message.extend_from_slice(b"\r\n");
+}
+
+/// A new random record id.
+fn record_id() -> String {
+    use std::hash::{BuildHasher, Hasher};
+
+    let random = || {
+        std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish()
+    };
+    let (high, low) = (random(), random());
+
+    format!(
+        "<urn:uuid:{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}>",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0x0fff,
+        (low >> 48) & 0x3fff | 0x8000,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+/// The UTC date of the record, ex: `2024-01-01T00:00:00Z`.
+fn warc_date(time: std::time::SystemTime) -> String {
+    let secs = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // civil date from the days since the epoch.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+/// Split the HTTP message into the head and body.
+fn split_http_message(message: &[u8]) -> Option<(&[u8], &[u8])> {
+    if let Some(position) = message.windows(4).position(|w| w == b"\r\n\r\n") {
+        Some((&message[..position], &message[position + 4..]))
+    } else {
+        message
+            .windows(2)
+            .position(|w| w == b"\n\n")
+            .map(|position| (&message[..position], &message[position + 2..]))
+    }
+}
+
+/// The header value contains the token.
+fn header_contains(headers: &HeaderMap, name: &str, token: &str) -> bool {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.to_ascii_lowercase().contains(token))
+}
+
+/// Decode a chunked transfer encoded body.
+fn decode_chunked(body: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(end) = rest.windows(2).position(|w| w == b"\r\n") {
+        let size = String::from_utf8_lossy(&rest[..end]);
+        let size = size.split(';').next().unwrap_or_default().trim();
+
+        let size = match usize::from_str_radix(size, 16) {
+            Ok(size) if size > 0 => size,
+            _ => break,
+        };
+
+        let start = end + 2;
+        let chunk_end = (start + size).min(rest.len());
+
+        decoded.extend_from_slice(&rest[start..chunk_end]);
+        rest = rest.get(chunk_end + 2..).unwrap_or_default();
+    }
+
+    decoded
+}
+
+/// Read all of the decoder.
+fn read_all(mut reader: impl Read) -> Option<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).ok()?;
+    Some(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{warc_date, WarcReader, WarcTransport, WarcWriter, MAX_WARC_RECORD_LENGTH};
+    use crate::utils::transport::Transport;
+
+    #[test]
+    fn test_warc_reader() {
+        let http = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nTransfer-Encoding: chunked\r\n\r\n5\r\n<a hr\r\n10\r\nef=\"/about\">A</a\r\n1\r\n>\r\n0\r\n\r\n";
+        let archive = format!(
+            "WARC/1.1\r\nWARC-Type: warcinfo\r\nContent-Length: 0\r\n\r\n\r\n\r\nWARC/1.1\r\nWARC-Type: response\r\nWARC-Target-URI: <https://example.com/>\r\nContent-Length: {}\r\n\r\n{}\r\n\r\n",
+            http.len(),
+            http
+        );
+
+        let records = WarcReader::new(archive.as_bytes())
+            .collect::<std::io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].warc_type(), Some("warcinfo"));
+        assert!(records[0].http_response().is_none());
+        assert_eq!(records[1].target_uri(), Some("https://example.com/"));
+
+        let response = records[1].http_response().unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, b"<a href=\"/about\">A</a>");
+        assert!(response.headers.get("transfer-encoding").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_warc_writer() {
+        let path = std::env::temp_dir().join(format!(
+            "spider_test_warc_writer_{}.warc",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut request_headers = crate::client::header::HeaderMap::new();
+        request_headers.insert("user-agent", "spider".parse().unwrap());
+
+        let writer = WarcWriter::create(&path)
+            .unwrap()
+            .with_request_headers(request_headers.clone());
+        let mut headers = crate::client::header::HeaderMap::new();
+        headers.insert("content-type", "text/html".parse().unwrap());
+        headers.insert("content-encoding", "gzip".parse().unwrap());
+
+        writer
+            .write_exchange(
+                "https://example.com/about?a=1",
+                crate::client::StatusCode::OK,
+                "HTTP/1.1",
+                &headers,
+                b"<p>About</p>",
+                None,
+            )
+            .unwrap();
+
+        request_headers.insert("content-type", "application/json".parse().unwrap());
+
+        let request = crate::page::CrawlRequest::new("https://example.com/search")
+            .with_method(reqwest::Method::POST)
+            .with_headers(request_headers)
+            .with_body(r#"{"q":"a"}"#);
+
+        writer
+            .write_exchange(
+                "https://example.com/search",
+                crate::client::StatusCode::OK,
+                "HTTP/1.1",
+                &headers,
+                b"<p>Results</p>",
+                Some(&request),
+            )
+            .unwrap();
+
+        writer.flush().await;
+
+        let records = WarcReader::open(&path)
+            .unwrap()
+            .collect::<std::io::Result<Vec<_>>>();
+        let _ = std::fs::remove_file(&path);
+        let records = records.unwrap();
+
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[0].warc_type(), Some("warcinfo"));
+        assert_eq!(
+            records[2].header("WARC-Concurrent-To"),
+            records[1].header("WARC-Record-ID")
+        );
+        assert_eq!(
+            records[2].content,
+            b"GET /about?a=1 HTTP/1.1\r\nhost: example.com\r\nuser-agent: spider\r\n\r\n"
+        );
+        assert_eq!(
+            records[4].content,
+            b"POST /search HTTP/1.1\r\nhost: example.com\r\nuser-agent: spider\r\ncontent-type: application/json\r\ncontent-length: 9\r\n\r\n{\"q\":\"a\"}"
+        );
+
+        let response = records[1].http_response().unwrap();
+
+        assert_eq!(response.body, b"<p>About</p>");
+        assert!(response.headers.get("content-encoding").is_none());
+        assert_eq!(warc_date(std::time::UNIX_EPOCH), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_warc_reader_length() {
+        let archive = format!(
+            "WARC/1.1\r\nWARC-Type: resource\r\nContent-Length: {}\r\n\r\nshort",
+            MAX_WARC_RECORD_LENGTH + 1
+        );
+
+        let err = WarcReader::new(archive.as_bytes())
+            .read_record()
+            .unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn test_warc_transport() {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!(
+            "spider_test_warc_transport_{}.warc",
+            std::process::id()
+        ));
+        let gz_path = path.with_extension("warc.gz");
+        let _ = std::fs::remove_file(&path);
+
+        let writer = WarcWriter::create(&path).unwrap();
+        let mut headers = crate::client::header::HeaderMap::new();
+        headers.insert("content-type", "text/css".parse().unwrap());
+
+        writer
+            .write_exchange(
+                "https://example.com/main.css",
+                crate::client::StatusCode::OK,
+                "HTTP/1.1",
+                &headers,
+                b"p {}",
+                None,
+            )
+            .unwrap();
+
+        headers.insert("content-type", "text/html".parse().unwrap());
+
+        writer
+            .write_exchange(
+                "https://example.com/",
+                crate::client::StatusCode::OK,
+                "HTTP/1.1",
+                &headers,
+                b"<p>Home</p>",
+                None,
+            )
+            .unwrap();
+
+        writer.flush().await;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&std::fs::read(&path).unwrap()).unwrap();
+        std::fs::write(&gz_path, encoder.finish().unwrap()).unwrap();
+
+        let fetched = [&path, &gz_path].map(|path| {
+            let transport = WarcTransport::open(path).unwrap();
+
+            (
+                transport.first_html().map(String::from),
+                transport.fetch("https://example.com/").unwrap(),
+                transport.fetch("https://example.com/main.css").unwrap(),
+                transport.fetch("https://example.com/missing").unwrap(),
+            )
+        });
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&gz_path);
+
+        for (first_html, home, css, missing) in fetched {
+            assert_eq!(first_html.as_deref(), Some("https://example.com/"));
+            assert_eq!(home.body, b"<p>Home</p>");
+            assert_eq!(css.body, b"p {}");
+            assert_eq!(missing.status, crate::client::StatusCode::NOT_FOUND);
+        }
+    }
+}
@@ -1,27 +1,195 @@
 use hashbrown::{hash_map::Entry, HashMap};
 use lazy_static::lazy_static;
 use log::{self, warn};
-use scraper::{ElementRef, Html, Selector};
+use scraper::{ElementRef, Html, Node, Selector};
 use std::{fmt::Debug, hash::Hash};
 use sxd_document::parser;
 use sxd_xpath::evaluate_xpath;
 use tokio_stream::StreamExt;
 
-/// The type of selectors that can be used to query.
+/// The type of selectors that can be used to query. Build with `build_selectors` or `Default` and set the options with the `with_` methods.
 #[derive(Default, Debug, Clone)]
 pub struct DocumentSelectors<K> {
     /// CSS Selectors.
     pub css: HashMap<K, Vec<Selector>>,
     /// XPath Selectors.
     pub xpath: HashMap<K, Vec<String>>,
+    /// Collapse the whitespace and trim the extracted text.
+    normalize: bool,
+    /// The max matches to extract for every key. If the value is 0 there is no limit.
+    max_matches: usize,
+    /// The max matches to extract by key overriding `max_matches`. If the value is 0 there is no limit.
+    key_max_matches: HashMap<K, usize>,
+    /// Skip the elements that match or are inside a match of the selectors, ex: `nav`, `aside`, and `footer`.
+    exclude: Vec<Selector>,
+    /// The order of the CSS and XPath values for a key that has both selectors.
+    merge_order: SelectorMergeOrder,
+    /// The strategy for the selectors of every key.
+    strategy: SelectorStrategy,
+    /// The strategy for the selectors by key overriding `strategy`.
+    key_strategy: HashMap<K, SelectorStrategy>,
+}
+
+/// How the values of the selectors for a key are combined.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorStrategy {
+    /// The values of all of the matching selectors.
+    #[default]
+    All,
+    /// The values of the first selector that matches skipping the rest as fallbacks, ex: `.price`, `.product-price`, and `[itemprop=price]`.
+    /// The selectors are tried in the order of the list, build with `build_selectors_base` and a `Vec` of selectors or the `indexset` feature to keep the order.
+    FirstMatch,
+}
+
+/// The order of the extracted values for a key with both CSS and XPath selectors.
+/// The values of each kind keep the order of the selectors and the CSS values follow the document order for every selector.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorMergeOrder {
+    /// The CSS values followed by the XPath values.
+    #[default]
+    CssFirst,
+    /// The XPath values followed by the CSS values.
+    XPathFirst,
+}
+
+impl<K> DocumentSelectors<K> {
+    /// Collapse the whitespace and trim the extracted text. The raw text is extracted by default.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Stop extracting a key after the max matches, ex: `1` for the first image. If the value is 0 there is no limit.
+    pub fn with_max_matches(mut self, max_matches: usize) -> Self {
+        self.max_matches = max_matches;
+        self
+    }
+
+    /// Skip the elements that match or are inside a match of the selectors. The excluded subtrees are removed from the extracted text.
+    pub fn with_exclude(mut self, exclude: Vec<Selector>) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    /// Set the strategy for the selectors of every key. The values of all of the matching selectors are extracted by default.
+    pub fn with_strategy(mut self, strategy: SelectorStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set the order of the CSS and XPath values for a key that has both selectors. The CSS values are first by default.
+    pub fn with_merge_order(mut self, merge_order: SelectorMergeOrder) -> Self {
+        self.merge_order = merge_order;
+        self
+    }
+
+    /// The element matches or is inside a match of the exclusion selectors.
+    fn is_excluded(&self, element: &ElementRef) -> bool {
+        !self.exclude.is_empty()
+            && std::iter::once(*element)
+                .chain(element.ancestors().filter_map(ElementRef::wrap))
+                .any(|e| self.exclude.iter().any(|s| s.matches(&e)))
+    }
+}
+
+impl<K: Eq + Hash> DocumentSelectors<K> {
+    /// Stop extracting the key after the max matches overriding `max_matches`. If the value is 0 there is no limit.
+    pub fn with_key_max_matches(mut self, key: K, max_matches: usize) -> Self {
+        self.key_max_matches.insert(key, max_matches);
+        self
+    }
+
+    /// Set the strategy for the selectors of the key overriding `strategy`.
+    pub fn with_key_strategy(mut self, key: K, strategy: SelectorStrategy) -> Self {
+        self.key_strategy.insert(key, strategy);
+        self
+    }
+
+    /// The max matches to extract for the key.
+    fn max_matches_for(&self, key: &K) -> usize {
+        self.key_max_matches
+            .get(key)
+            .copied()
+            .unwrap_or(self.max_matches)
+    }
+
+    /// The key already has a value and only the first matching selector is used.
+    fn first_matched(&self, key: &K, map: &CSSQueryMap) -> bool
+    where
+        K: AsRef<str>,
+    {
+        self.key_strategy.get(key).copied().unwrap_or(self.strategy) == SelectorStrategy::FirstMatch
+            && extracted_len(map, key.as_ref()) > 0
+    }
 }
 
 /// Extracted content from CSS query selectors.
 type CSSQueryMap = HashMap<String, Vec<String>>;
 
+/// The max amount of compiled selectors to keep in the cache.
+const SELECTOR_CACHE_CAPACITY: usize = 1024;
+
+/// The compiled selectors evicting the least recently used selector once full.
+#[derive(Default)]
+struct SelectorCache {
+    /// The counter of the cache uses to order the selectors by the last use.
+    tick: u64,
+    /// The compiled selectors by selector string with the tick of the last use.
+    entries: HashMap<String, (Selector, u64)>,
+}
+
 lazy_static! {
     /// Xpath factory.
     static ref XPATH_FACTORY: sxd_xpath::Factory = sxd_xpath::Factory::new();
+    /// The compiled selectors cache.
+    static ref SELECTOR_CACHE: std::sync::Mutex<SelectorCache> =
+        std::sync::Mutex::new(SelectorCache::default());
+    /// The table selector.
+    static ref TABLE_SELECTOR: Selector = Selector::parse("table").unwrap();
+    /// The table row selector.
+    static ref TABLE_ROW_SELECTOR: Selector = Selector::parse("tr").unwrap();
+}
+
+/// Parse the CSS selector re-using the compiled selector of an identical string. The least recently used selector is evicted once the cache is full.
+pub fn parse_selector_cached(
+    selector: &str,
+) -> Result<Selector, scraper::error::SelectorErrorKind<'_>> {
+    let mut cache = match SELECTOR_CACHE.lock() {
+        Ok(cache) => cache,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let SelectorCache { tick, entries } = &mut *cache;
+
+    *tick += 1;
+
+    if let Some((compiled, last_used)) = entries.get_mut(selector) {
+        *last_used = *tick;
+        return Ok(compiled.clone());
+    }
+
+    let compiled = Selector::parse(selector)?;
+
+    if entries.len() >= SELECTOR_CACHE_CAPACITY {
+        if let Some(oldest) = entries
+            .iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(key, _)| key.clone())
+        {
+            entries.remove(&oldest);
+        }
+    }
+
+    entries.insert(selector.to_string(), (compiled.clone(), *tick));
+
+    Ok(compiled)
+}
+
+/// Clear the compiled selectors cache.
+pub fn clear_selector_cache() {
+    match SELECTOR_CACHE.lock() {
+        Ok(mut cache) => cache.entries.clear(),
+        Err(poisoned) => poisoned.into_inner().entries.clear(),
+    }
 }
 
 /// Check if a selector is a valid xpath
@@ -33,50 +201,275 @@ fn is_valid_xpath(expression: &str) -> bool {
     }
 }
 
+/// The XPath 2.0 functions rewritten to XPath 1.0 expressions.
+const XPATH2_SHIMMED_FUNCTIONS: [&str; 3] = ["lower-case", "upper-case", "ends-with"];
+
+/// The XPath 2.0 functions that have no XPath 1.0 equivalent.
+const XPATH2_UNSUPPORTED_FUNCTIONS: [&str; 12] = [
+    "matches",
+    "replace",
+    "tokenize",
+    "string-join",
+    "distinct-values",
+    "index-of",
+    "exists",
+    "empty",
+    "abs",
+    "avg",
+    "min",
+    "max",
+];
+
+/// The selector could not be used for extracting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorError {
+    /// The selector is not a valid CSS or XPath selector.
+    Invalid(String),
+    /// The selector is valid XPath 2.0 using a function that is not supported by the XPath 1.0 engine.
+    UnsupportedXPath {
+        /// The selector.
+        selector: String,
+        /// The unsupported function.
+        function: String,
+    },
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorError::Invalid(selector) => write!(f, "invalid selector '{}'", selector),
+            SelectorError::UnsupportedXPath { selector, function } => write!(
+                f,
+                "unsupported XPath 2.0 function '{}()' in selector '{}'",
+                function, selector
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+/// The character can be part of an XPath function name.
+fn is_xpath_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || c == '.'
+}
+
+/// Split the arguments of the function call starting at the open parenthesis returning the arguments and the index after the closing parenthesis.
+fn split_xpath_args(chars: &[char], open: usize) -> Option<(Vec<String>, usize)> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut i = open + 1;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                current.push(c);
+            }
+            None => match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                '(' | '[' => {
+                    depth += 1;
+                    current.push(c);
+                }
+                ')' if depth == 0 => {
+                    if !current.trim().is_empty() || !args.is_empty() {
+                        args.push(current);
+                    }
+                    return Some((args, i + 1));
+                }
+                ')' | ']' => {
+                    depth = depth.saturating_sub(1);
+                    current.push(c);
+                }
+                ',' if depth == 0 => args.push(std::mem::take(&mut current)),
+                _ => current.push(c),
+            },
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+/// Rewrite the XPath 2.0 string functions to XPath 1.0 expressions.
+fn shim_xpath(selector: &str, expression: &str) -> Result<String, SelectorError> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut output = String::with_capacity(expression.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '"' || c == '\'' {
+            let end = chars[i + 1..]
+                .iter()
+                .position(|n| *n == c)
+                .map_or(chars.len(), |p| i + p + 2);
+            output.extend(&chars[i..end]);
+            i = end;
+        } else if c.is_alphabetic() && (i == 0 || !is_xpath_name_char(chars[i - 1])) {
+            let start = i;
+
+            while i < chars.len() && is_xpath_name_char(chars[i]) {
+                i += 1;
+            }
+
+            let name: String = chars[start..i].iter().collect();
+            let open = chars[i..]
+                .iter()
+                .position(|n| !n.is_whitespace())
+                .map(|p| i + p)
+                .filter(|p| chars[*p] == '(');
+
+            match open {
+                Some(_) if XPATH2_UNSUPPORTED_FUNCTIONS.contains(&name.as_str()) => {
+                    return Err(SelectorError::UnsupportedXPath {
+                        selector: selector.to_string(),
+                        function: name,
+                    })
+                }
+                Some(open) if XPATH2_SHIMMED_FUNCTIONS.contains(&name.as_str()) => {
+                    let (args, end) = split_xpath_args(&chars, open)
+                        .ok_or_else(|| SelectorError::Invalid(selector.to_string()))?;
+                    let args = args
+                        .iter()
+                        .map(|arg| shim_xpath(selector, arg.trim()))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    let shimmed = match (name.as_str(), args.as_slice()) {
+                        ("lower-case", [value]) => format!(
+                            "translate({}, 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', 'abcdefghijklmnopqrstuvwxyz')",
+                            value
+                        ),
+                        ("upper-case", [value]) => format!(
+                            "translate({}, 'abcdefghijklmnopqrstuvwxyz', 'ABCDEFGHIJKLMNOPQRSTUVWXYZ')",
+                            value
+                        ),
+                        ("ends-with", [value, suffix]) => format!(
+                            "(substring({value}, string-length({value}) - string-length({suffix}) + 1) = {suffix})",
+                            value = value,
+                            suffix = suffix
+                        ),
+                        _ => return Err(SelectorError::Invalid(selector.to_string())),
+                    };
+
+                    output.push_str(&shimmed);
+                    i = end;
+                }
+                _ => output.push_str(&name),
+            }
+        } else {
+            output.push(c);
+            i += 1;
+        }
+    }
+
+    Ok(output)
+}
+
+/// Parse the XPath selector rewriting the common XPath 2.0 string functions `lower-case`, `upper-case`, and `ends-with` to XPath 1.0.
+pub fn parse_xpath_selector(selector: &str) -> Result<String, SelectorError> {
+    let expression = shim_xpath(selector, selector)?;
+
+    if is_valid_xpath(&expression) {
+        Ok(expression)
+    } else {
+        Err(SelectorError::Invalid(selector.to_string()))
+    }
+}
+
+/// Validate the CSS or XPath selector distinguishing invalid selectors from XPath 2.0 that can not be supported.
+pub fn validate_selector(selector: &str) -> Result<(), SelectorError> {
+    if parse_selector_cached(selector).is_ok() {
+        Ok(())
+    } else {
+        parse_xpath_selector(selector).map(|_| ())
+    }
+}
+
 /// Async stream CSS query selector map.
 pub async fn css_query_select_map_streamed<K>(
     html: &str,
     selectors: &DocumentSelectors<K>,
 ) -> CSSQueryMap
+where
+    K: AsRef<str> + Eq + Hash + Sized,
+{
+    if selectors.css.len() == 1 && selectors.xpath.is_empty() {
+        css_query_select_single(html, selectors)
+    } else {
+        css_query_select_stream(html, selectors).await
+    }
+}
+
+/// Extract the selectors streaming the CSS selectors over the boxed document.
+async fn css_query_select_stream<K>(html: &str, selectors: &DocumentSelectors<K>) -> CSSQueryMap
 where
     K: AsRef<str> + Eq + Hash + Sized,
 {
     let mut map: CSSQueryMap = HashMap::new();
 
+    // the XPath document is not held across the await points to keep the future Send.
+    if selectors.merge_order == SelectorMergeOrder::XPathFirst && !selectors.xpath.is_empty() {
+        extract_xpath_map(
+            &parse_xpath_document(html).as_document(),
+            selectors,
+            &mut map,
+        );
+    }
+
     if !selectors.css.is_empty() {
         let mut stream = tokio_stream::iter(&selectors.css);
         let fragment = Box::new(Html::parse_document(html));
 
         while let Some(selector) = stream.next().await {
-            for s in selector.1 {
-                for element in fragment.select(s) {
-                    process_selector::<K>(element, selector.0, &mut map);
-                }
-            }
+            extract_css(
+                |s| fragment.select(s),
+                selector.0,
+                selector.1,
+                selectors,
+                &mut map,
+            );
         }
     }
 
-    if !selectors.xpath.is_empty() {
-        if let Ok(package) = parser::parse(html) {
-            let document = Box::new(package.as_document());
+    if selectors.merge_order == SelectorMergeOrder::CssFirst && !selectors.xpath.is_empty() {
+        extract_xpath_map(
+            &parse_xpath_document(html).as_document(),
+            selectors,
+            &mut map,
+        );
+    }
 
-            for selector in selectors.xpath.iter() {
-                for s in selector.1 {
-                    if let Ok(value) = evaluate_xpath(&document, s) {
-                        let text = value.into_string();
+    for items in map.values_mut() {
+        items.dedup();
+    }
 
-                        if !text.is_empty() {
-                            match map.entry(selector.0.as_ref().to_string()) {
-                                Entry::Occupied(mut entry) => entry.get_mut().push(text),
-                                Entry::Vacant(entry) => {
-                                    entry.insert(vec![text]);
-                                }
-                            }
-                        }
-                    };
-                }
-            }
-        };
+    map
+}
+
+/// Extract the selectors of the only CSS key without the stream or boxing the document, ex: one field from every page.
+fn css_query_select_single<K>(html: &str, selectors: &DocumentSelectors<K>) -> CSSQueryMap
+where
+    K: AsRef<str> + Eq + Hash + Sized,
+{
+    let mut map: CSSQueryMap = HashMap::new();
+
+    if let Some((name, list)) = selectors.css.iter().next() {
+        let fragment = Html::parse_document(html);
+
+        extract_css(|s| fragment.select(s), name, list, selectors, &mut map);
     }
 
     for items in map.values_mut() {
@@ -92,50 +485,430 @@ where
     K: AsRef<str> + Eq + Hash + Sized,
 {
     let mut map: CSSQueryMap = HashMap::new();
+    let fragment = (!selectors.css.is_empty()).then(|| Html::parse_document(html));
+    let package = (!selectors.xpath.is_empty()).then(|| parse_xpath_document(html));
 
-    if !selectors.css.is_empty() {
-        let fragment = Box::new(Html::parse_document(html));
+    extract_ordered(fragment.as_ref(), package.as_ref(), selectors, &mut map);
+
+    map
+}
+
+/// Sync CSS query selector map with the amount of elements matched by key. Every configured key is returned even when the selectors matched nothing, ex: `(0, vec![])` for a broken selector.
+pub fn css_query_select_map_counts<K>(
+    html: &str,
+    selectors: &DocumentSelectors<K>,
+) -> HashMap<K, (usize, Vec<String>)>
+where
+    K: AsRef<str> + Eq + Hash + Clone + Sized,
+{
+    let mut map: CSSQueryMap = HashMap::new();
+    let mut counts: HashMap<K, (usize, Vec<String>)> = HashMap::new();
+    let fragment = (!selectors.css.is_empty()).then(|| Html::parse_document(html));
+    let package = (!selectors.xpath.is_empty()).then(|| parse_xpath_document(html));
 
+    extract_ordered(fragment.as_ref(), package.as_ref(), selectors, &mut map);
+
+    if let Some(ref fragment) = fragment {
         for selector in selectors.css.iter() {
-            for s in selector.1 {
-                for element in fragment.select(s) {
-                    process_selector::<K>(element, selector.0, &mut map);
+            let count: usize = selector
+                .1
+                .iter()
+                .map(|s| {
+                    fragment
+                        .select(s)
+                        .filter(|element| !selectors.is_excluded(element))
+                        .count()
+                })
+                .sum();
+
+            counts.entry(selector.0.clone()).or_default().0 += count;
+        }
+    }
+
+    if let Some(ref package) = package {
+        let document = package.as_document();
+
+        for selector in selectors.xpath.iter() {
+            let count: usize = selector
+                .1
+                .iter()
+                .map(|s| match evaluate_xpath(&document, s) {
+                    Ok(sxd_xpath::Value::Nodeset(nodes)) => nodes.size(),
+                    Ok(value) => usize::from(!value.into_string().is_empty()),
+                    Err(_) => 0,
+                })
+                .sum();
+
+            counts.entry(selector.0.clone()).or_default().0 += count;
+        }
+    }
+
+    for (name, entry) in counts.iter_mut() {
+        if let Some(values) = map.remove(name.as_ref()) {
+            entry.1 = values;
+        }
+    }
+
+    counts
+}
+
+/// Extract the CSS and XPath selectors in the merge order of the selectors.
+fn extract_ordered<K>(
+    fragment: Option<&Html>,
+    package: Option<&sxd_document::Package>,
+    selectors: &DocumentSelectors<K>,
+    map: &mut CSSQueryMap,
+) where
+    K: AsRef<str> + Eq + Hash + Sized,
+{
+    let document = package.map(|package| package.as_document());
+
+    if selectors.merge_order == SelectorMergeOrder::XPathFirst {
+        if let Some(ref document) = document {
+            extract_xpath_map(document, selectors, map);
+        }
+    }
+
+    if let Some(fragment) = fragment {
+        for selector in selectors.css.iter() {
+            extract_css(
+                |s| fragment.select(s),
+                selector.0,
+                selector.1,
+                selectors,
+                map,
+            );
+        }
+    }
+
+    if selectors.merge_order == SelectorMergeOrder::CssFirst {
+        if let Some(ref document) = document {
+            extract_xpath_map(document, selectors, map);
+        }
+    }
+}
+
+/// Extract all of the XPath selectors.
+fn extract_xpath_map<K>(
+    document: &sxd_document::dom::Document,
+    selectors: &DocumentSelectors<K>,
+    map: &mut CSSQueryMap,
+) where
+    K: AsRef<str> + Eq + Hash + Sized,
+{
+    for selector in selectors.xpath.iter() {
+        extract_xpath(document, selector.0, selector.1, selectors, map);
+    }
+}
+
+/// Parse the html into a document for XPath. Html that is not well-formed XML, ex: unclosed tags, is parsed as html and converted to XML.
+fn parse_xpath_document(html: &str) -> sxd_document::Package {
+    match parser::parse(html) {
+        Ok(package) => package,
+        _ => {
+            let package = sxd_document::Package::new();
+            let fragment = Html::parse_document(html);
+
+            {
+                let document = package.as_document();
+                let root = fragment.root_element();
+                let element = document.create_element(root.value().name());
+
+                document.root().append_child(element);
+                append_xpath_children(&document, element, root);
+            }
+
+            package
+        }
+    }
+}
+
+/// Append the children of the html element to the XML element.
+fn append_xpath_children<'d>(
+    document: &sxd_document::dom::Document<'d>,
+    parent: sxd_document::dom::Element<'d>,
+    html: ElementRef,
+) {
+    for attr in html.value().attrs() {
+        parent.set_attribute_value(attr.0, attr.1);
+    }
+
+    for child in html.children() {
+        match child.value() {
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    let element = document.create_element(child.value().name());
+
+                    parent.append_child(element);
+                    append_xpath_children(document, element, child);
                 }
             }
+            Node::Text(text) => parent.append_child(document.create_text(text)),
+            _ => (),
         }
     }
+}
 
-    if !selectors.xpath.is_empty() {
-        if let Ok(package) = parser::parse(html) {
-            let document = package.as_document();
+/// The amount of values extracted for the key.
+fn extracted_len(map: &CSSQueryMap, name: &str) -> usize {
+    map.get(name).map_or(0, |items| items.len())
+}
 
-            for selector in selectors.xpath.iter() {
-                for s in selector.1 {
-                    if let Ok(value) = evaluate_xpath(&document, s) {
-                        let text = value.into_string();
+/// Extract the CSS selectors of the key stopping at the max matches.
+fn extract_css<'a, 'b, K, F, I>(
+    select: F,
+    name: &K,
+    list: &'b [Selector],
+    selectors: &DocumentSelectors<K>,
+    map: &mut CSSQueryMap,
+) where
+    K: AsRef<str> + Eq + Hash + Sized,
+    F: Fn(&'b Selector) -> I,
+    I: Iterator<Item = ElementRef<'a>>,
+{
+    let max_matches = selectors.max_matches_for(name);
 
-                        if !text.is_empty() {
-                            match map.entry(selector.0.as_ref().to_string()) {
-                                Entry::Occupied(mut entry) => entry.get_mut().push(text),
-                                Entry::Vacant(entry) => {
-                                    entry.insert(vec![text]);
-                                }
-                            }
-                        }
-                    };
+    for s in list {
+        if selectors.first_matched(name, map) {
+            return;
+        }
+
+        for element in select(s) {
+            if max_matches > 0 && extracted_len(map, name.as_ref()) >= max_matches {
+                return;
+            }
+            process_selector::<K>(element, name, selectors, map);
+        }
+    }
+}
+
+/// Extract the XPath selectors of the key stopping at the max matches.
+fn extract_xpath<K>(
+    document: &sxd_document::dom::Document,
+    name: &K,
+    list: &[String],
+    selectors: &DocumentSelectors<K>,
+    map: &mut CSSQueryMap,
+) where
+    K: AsRef<str> + Eq + Hash + Sized,
+{
+    let max_matches = selectors.max_matches_for(name);
+
+    for s in list {
+        if (max_matches > 0 && extracted_len(map, name.as_ref()) >= max_matches)
+            || selectors.first_matched(name, map)
+        {
+            return;
+        }
+
+        if let Ok(value) = evaluate_xpath(document, s) {
+            let text = if selectors.normalize {
+                normalize_text(&value.into_string())
+            } else {
+                value.into_string()
+            };
+
+            if !text.is_empty() {
+                match map.entry(name.as_ref().to_string()) {
+                    Entry::Occupied(mut entry) => entry.get_mut().push(text),
+                    Entry::Vacant(entry) => {
+                        entry.insert(vec![text]);
+                    }
                 }
             }
         };
     }
+}
 
-    map
+/// Nested CSS query selector map. The child selectors run within each match of the parent selector returning a map for every parent, ex: the `.title` and `.price` of every `.product`.
+/// The XPath selectors are not used.
+pub fn css_query_select_nested<K>(
+    html: &str,
+    parent: &Selector,
+    selectors: &DocumentSelectors<K>,
+) -> Vec<CSSQueryMap>
+where
+    K: AsRef<str> + Eq + Hash + Sized,
+{
+    let fragment = Html::parse_document(html);
+
+    fragment
+        .select(parent)
+        .filter(|element| !selectors.is_excluded(element))
+        .map(|element| {
+            let mut map: CSSQueryMap = HashMap::new();
+
+            for selector in selectors.css.iter() {
+                extract_css(
+                    |s| element.select(s),
+                    selector.0,
+                    selector.1,
+                    selectors,
+                    &mut map,
+                );
+            }
+
+            map
+        })
+        .collect()
+}
+
+/// The rows of a html table keyed by the header cells.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct TableData {
+    /// The header cells from the `<thead>` or the first row of `<th>` cells. The column number is used for the empty or missing header cells.
+    pub headers: Vec<String>,
+    /// The body rows with the cells keyed by the headers. The missing cells are empty.
+    pub rows: Vec<HashMap<String, String>>,
+}
+
+/// Extract the `<table>` elements of the html into rows keyed by the header cells. The `colspan` and `rowspan` cells are repeated for every column and row they span.
+pub fn extract_tables(html: &str) -> Vec<TableData> {
+    let fragment = Html::parse_document(html);
+
+    fragment
+        .select(&TABLE_SELECTOR)
+        .map(|table| {
+            let mut header_rows = 0;
+            let mut rows: Vec<(bool, Vec<String>)> = Vec::new();
+            // the cell text with the remaining rows spanned by column.
+            let mut spans: Vec<Option<(String, usize)>> = Vec::new();
+
+            // the rows of nested tables are skipped.
+            for row in table.select(&TABLE_ROW_SELECTOR).filter(|row| {
+                row.ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(|e| e.value().name() == "table")
+                    .is_some_and(|e| e.id() == table.id())
+            }) {
+                let in_head = row
+                    .parent()
+                    .and_then(ElementRef::wrap)
+                    .is_some_and(|e| e.value().name() == "thead");
+                let mut cells = Vec::new();
+                let mut all_headers = true;
+
+                for cell in row
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|e| matches!(e.value().name(), "td" | "th"))
+                {
+                    take_table_spans(&mut spans, &mut cells);
+
+                    let text = cell
+                        .text()
+                        .collect::<String>()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let span = |name| {
+                        cell.attr(name)
+                            .and_then(|v| v.trim().parse::<usize>().ok())
+                            .unwrap_or(1)
+                            .clamp(1, 1000)
+                    };
+                    let rowspan = span("rowspan");
+
+                    all_headers &= cell.value().name() == "th";
+
+                    for _ in 0..span("colspan") {
+                        if spans.len() <= cells.len() {
+                            spans.resize(cells.len() + 1, None);
+                        }
+                        if rowspan > 1 {
+                            spans[cells.len()] = Some((text.clone(), rowspan - 1));
+                        }
+                        cells.push(text.clone());
+                    }
+                }
+
+                // the spans continuing after the last cell of the row.
+                take_table_spans(&mut spans, &mut cells);
+
+                if in_head || (rows.is_empty() && all_headers && !cells.is_empty()) {
+                    header_rows += 1;
+                }
+
+                rows.push((in_head, cells));
+            }
+
+            let header = rows
+                .iter()
+                .take(header_rows)
+                .rev()
+                .map(|(_, cells)| cells)
+                .next();
+            let mut headers: Vec<String> = Vec::new();
+
+            if let Some(header) = header {
+                for (i, cell) in header.iter().enumerate() {
+                    let key = if cell.is_empty() || headers.contains(cell) {
+                        i.to_string()
+                    } else {
+                        cell.clone()
+                    };
+                    headers.push(key);
+                }
+            }
+
+            let columns = rows
+                .iter()
+                .skip(header_rows)
+                .map(|(_, cells)| cells.len())
+                .max()
+                .unwrap_or_default();
+
+            for i in headers.len()..columns {
+                headers.push(i.to_string());
+            }
+
+            let rows = rows
+                .into_iter()
+                .skip(header_rows)
+                .filter(|(in_head, cells)| !in_head && !cells.is_empty())
+                .map(|(_, mut cells)| {
+                    cells.resize(headers.len(), String::new());
+                    headers
+                        .iter()
+                        .cloned()
+                        .zip(cells)
+                        .collect::<HashMap<_, _>>()
+                })
+                .collect();
+
+            TableData { headers, rows }
+        })
+        .collect()
+}
+
+/// Push the cells spanned from the rows above at the current column.
+fn take_table_spans(spans: &mut [Option<(String, usize)>], cells: &mut Vec<String>) {
+    while let Some(span) = spans.get_mut(cells.len()) {
+        match span.take() {
+            Some((text, remaining)) => {
+                if remaining > 1 {
+                    *span = Some((text.clone(), remaining - 1));
+                }
+                cells.push(text);
+            }
+            _ => break,
+        }
+    }
 }
 
 /// Process a single element and update the map with the results.
-fn process_selector<K>(element: ElementRef, name: &K, map: &mut CSSQueryMap)
-where
+fn process_selector<K>(
+    element: ElementRef,
+    name: &K,
+    selectors: &DocumentSelectors<K>,
+    map: &mut CSSQueryMap,
+) where
     K: AsRef<str> + Eq + Hash + Sized,
 {
+    if selectors.is_excluded(&element) {
+        return;
+    }
+
     let name = name.as_ref();
     let element_name = element.value().name();
 
@@ -148,7 +921,7 @@ where
             "src"
         }) {
             Some(href) => href.into(),
-            _ => clean_element_text(&element),
+            _ => clean_element_text_excluding(&element, &selectors.exclude),
         }
     } else if element_name == "img" || element_name == "source" {
         let mut img_text = String::new();
@@ -176,7 +949,13 @@ where
 
         img_text
     } else {
-        clean_element_text(&element)
+        clean_element_text_excluding(&element, &selectors.exclude)
+    };
+
+    let text = if selectors.normalize {
+        normalize_text(&text)
+    } else {
+        text
     };
 
     if !text.is_empty() {
@@ -189,11 +968,214 @@ where
     }
 }
 
+/// A step of a JSON path.
+#[derive(Debug, Clone, PartialEq)]
+enum JsonPathStep {
+    /// The value of the key, ex: `.name` or `['name']`.
+    Child(String),
+    /// The values of the key at any depth, ex: `..name`.
+    Descendant(String),
+    /// The item of the array at the index, ex: `[0]`.
+    Index(usize),
+    /// All the items or values, ex: `[*]` or `.*`.
+    Wildcard,
+}
+
+/// Parse the JSON path into the steps. The leading `$` is optional.
+fn parse_json_path(path: &str) -> Vec<JsonPathStep> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let bytes = path.as_bytes();
+    let mut steps = Vec::new();
+    let mut i = 0;
+
+    let read_name = |start: usize| -> usize {
+        let mut end = start;
+        while end < bytes.len() && bytes[end] != b'.' && bytes[end] != b'[' {
+            end += 1;
+        }
+        end
+    };
+
+    while i < bytes.len() {
+        if path[i..].starts_with("..") {
+            let end = read_name(i + 2);
+            let name = &path[i + 2..end];
+            if !name.is_empty() {
+                steps.push(JsonPathStep::Descendant(name.into()));
+            }
+            i = end;
+        } else if bytes[i] == b'[' {
+            let end = match path[i..].find(']') {
+                Some(end) => i + end,
+                _ => break,
+            };
+            let inner = path[i + 1..end].trim();
+
+            if inner == "*" {
+                steps.push(JsonPathStep::Wildcard);
+            } else if let Ok(index) = inner.parse::<usize>() {
+                steps.push(JsonPathStep::Index(index));
+            } else {
+                let name = inner.trim_matches(|c| c == '\'' || c == '"');
+                steps.push(JsonPathStep::Child(name.into()));
+            }
+            i = end + 1;
+        } else {
+            let start = if bytes[i] == b'.' { i + 1 } else { i };
+            let end = read_name(start);
+            let name = &path[start..end];
+
+            if name == "*" {
+                steps.push(JsonPathStep::Wildcard);
+            } else if !name.is_empty() {
+                steps.push(JsonPathStep::Child(name.into()));
+            }
+            i = end.max(i + 1);
+        }
+    }
+
+    steps
+}
+
+/// Collect the values of the key at any depth.
+fn collect_json_descendants<'a>(
+    value: &'a serde_json::Value,
+    name: &str,
+    values: &mut Vec<&'a serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                if key == name {
+                    values.push(child);
+                }
+                collect_json_descendants(child, name, values);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_json_descendants(item, name, values);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Select the values of the JSON matching the path, ex: `$.data.items[*].url` or `$..url`. Supports the child, recursive descent, index, and wildcard steps of JSONPath.
+pub fn json_path_select<'a>(
+    value: &'a serde_json::Value,
+    path: &str,
+) -> Vec<&'a serde_json::Value> {
+    let mut current = vec![value];
+
+    for step in parse_json_path(path) {
+        let mut next = Vec::new();
+
+        for value in current {
+            match &step {
+                JsonPathStep::Child(name) => {
+                    if let Some(child) = value.get(name.as_str()) {
+                        next.push(child);
+                    }
+                }
+                JsonPathStep::Descendant(name) => collect_json_descendants(value, name, &mut next),
+                JsonPathStep::Index(index) => {
+                    if let Some(child) = value.get(*index) {
+                        next.push(child);
+                    }
+                }
+                JsonPathStep::Wildcard => match value {
+                    serde_json::Value::Object(map) => next.extend(map.values()),
+                    serde_json::Value::Array(items) => next.extend(items.iter()),
+                    _ => (),
+                },
+            }
+        }
+
+        current = next;
+    }
+
+    current
+}
+
+/// Get the text of a JSON value. The strings are used as is, the objects and arrays are serialized.
+fn json_value_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        value => Some(value.to_string()),
+    }
+}
+
+/// JSON path query selector map for the JSON responses mirroring the CSS query selector map. The values matched by every path of the key are collected. An invalid JSON body returns an empty map.
+pub fn json_query_select_map<K>(body: &[u8], selectors: &HashMap<K, Vec<String>>) -> CSSQueryMap
+where
+    K: AsRef<str> + Eq + Hash + Sized,
+{
+    let mut map: CSSQueryMap = HashMap::new();
+
+    let value = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("{}", e);
+            return map;
+        }
+    };
+
+    for (key, paths) in selectors.iter() {
+        for path in paths {
+            for text in json_path_select(&value, path)
+                .into_iter()
+                .filter_map(json_value_text)
+            {
+                match map.entry(key.as_ref().to_string()) {
+                    Entry::Occupied(mut entry) => entry.get_mut().push(text),
+                    Entry::Vacant(entry) => {
+                        entry.insert(vec![text]);
+                    }
+                }
+            }
+        }
+    }
+
+    map
+}
+
 /// get the text extracted.
 pub fn clean_element_text(element: &ElementRef) -> String {
     element.text().collect::<Vec<_>>().join(" ")
 }
 
+/// get the text extracted skipping the subtrees that match the exclusion selectors.
+pub fn clean_element_text_excluding(element: &ElementRef, exclude: &[Selector]) -> String {
+    if exclude.is_empty() {
+        return clean_element_text(element);
+    }
+
+    let excluded: hashbrown::HashSet<_> = exclude
+        .iter()
+        .flat_map(|s| element.select(s))
+        .map(|e| e.id())
+        .collect();
+
+    if excluded.is_empty() {
+        return clean_element_text(element);
+    }
+
+    element
+        .descendants()
+        .filter(|node| !node.ancestors().any(|a| excluded.contains(&a.id())))
+        .filter_map(|node| node.value().as_text().map(|text| &**text))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Normalize the extracted text collapsing the runs of whitespace and trimming. The html entities are decoded by the parser.
+pub fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 /// Build valid css selectors for extracting. The hashmap takes items with the key for the object key and the value is the css selector.
 pub fn build_selectors_base<K, V, S>(selectors: HashMap<K, S>) -> DocumentSelectors<K>
 where
@@ -209,22 +1191,20 @@ where
         let mut selectors_vec_xpath = Vec::new();
 
         for selector_str in selector_set {
-            match Selector::parse(selector_str.as_ref()) {
+            match parse_selector_cached(selector_str.as_ref()) {
                 Ok(selector) => selectors_vec.push(selector),
-                Err(err) => {
-                    if is_valid_xpath(selector_str.as_ref()) {
-                        selectors_vec_xpath.push(selector_str.as_ref().to_string())
-                    } else {
-                        warn!(
-                            "{}",
-                            format!(
-                                "Failed to parse selector '{}': {:?}",
-                                selector_str.as_ref(),
-                                err
-                            ),
-                        )
-                    }
-                }
+                Err(err) => match parse_xpath_selector(selector_str.as_ref()) {
+                    Ok(expression) => selectors_vec_xpath.push(expression),
+                    Err(SelectorError::Invalid(_)) => warn!(
+                        "{}",
+                        format!(
+                            "Failed to parse selector '{}': {:?}",
+                            selector_str.as_ref(),
+                            err
+                        ),
+                    ),
+                    Err(unsupported) => warn!("{}", unsupported),
+                },
             }
         }
 
@@ -248,6 +1228,13 @@ where
     DocumentSelectors {
         css: valid_selectors,
         xpath: valid_selectors_xpath,
+        normalize: false,
+        max_matches: 0,
+        key_max_matches: HashMap::new(),
+        exclude: Vec::new(),
+        merge_order: SelectorMergeOrder::CssFirst,
+        strategy: SelectorStrategy::All,
+        key_strategy: HashMap::new(),
     }
 }
 
@@ -339,3 +1326,273 @@ async fn test_xpath_query_select_map_streamed() {
 
     assert!(!data.is_empty(), "Xpath extraction failed",);
 }
+
+#[test]
+fn test_css_query_select_map_normalize() {
+    let map = QueryCSSMap::from([("title", QueryCSSSelectSet::from([".title"]))]);
+    let html = r#"<html><body><h1 class="title">
+            Tom&#39;s
+
+            <span>Caf&eacute; &amp;amp; Bar</span>	</h1></body></html>"#;
+
+    let raw = css_query_select_map(html, &build_selectors(map.clone()));
+    let data = css_query_select_map(html, &build_selectors(map).with_normalize(true));
+
+    assert!(raw["title"][0].contains("\n"));
+    assert_eq!(data["title"], vec!["Tom's Café &amp; Bar"]);
+}
+
+#[test]
+fn test_css_query_select_map_max_matches() {
+    let map = QueryCSSMap::from([
+        ("price", QueryCSSSelectSet::from([".price"])),
+        ("image", QueryCSSSelectSet::from(["img"])),
+    ]);
+    let html = r#"<html><body><img src="/a.png"><img src="/b.png"><p class="price">1</p><p class="price">2</p><p class="price">3</p></body></html>"#;
+
+    let data = css_query_select_map(
+        html,
+        &build_selectors(map)
+            .with_max_matches(2)
+            .with_key_max_matches("image", 1),
+    );
+
+    assert_eq!(data["price"], vec!["1", "2"]);
+    assert_eq!(data["image"], vec!["[/a.png]"]);
+}
+
+#[test]
+fn test_css_query_select_map_exclude() {
+    let map = QueryCSSMap::from([
+        ("content", QueryCSSSelectSet::from(["article"])),
+        ("links", QueryCSSSelectSet::from(["a"])),
+    ]);
+    let html = r#"<html><body><article><p>Story</p><nav><a href="/">Home</a></nav><p>End</p></article><footer><a href="/about">About</a></footer></body></html>"#;
+
+    let data = css_query_select_map(
+        html,
+        &build_selectors(map).with_exclude(vec![
+            Selector::parse("nav").unwrap(),
+            Selector::parse("footer").unwrap(),
+        ]),
+    );
+
+    assert_eq!(data["content"], vec!["Story End"]);
+    assert!(!data.contains_key("links"));
+}
+
+#[test]
+fn test_css_query_select_nested() {
+    let map = QueryCSSMap::from([
+        ("title", QueryCSSSelectSet::from([".title"])),
+        ("price", QueryCSSSelectSet::from([".price"])),
+    ]);
+    let html = r#"<html><body>
+        <div class="product"><h2 class="title">Shoe</h2><span class="price">10</span></div>
+        <div class="product"><h2 class="title">Hat</h2></div>
+    </body></html>"#;
+
+    let data = css_query_select_nested(
+        html,
+        &Selector::parse(".product").unwrap(),
+        &build_selectors(map),
+    );
+
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0]["title"], vec!["Shoe"]);
+    assert_eq!(data[0]["price"], vec!["10"]);
+    assert_eq!(data[1]["title"], vec!["Hat"]);
+    assert!(!data[1].contains_key("price"));
+}
+
+#[test]
+fn test_parse_selector_cached() {
+    let selector = parse_selector_cached(".cached-price").unwrap();
+
+    assert_eq!(
+        parse_selector_cached(".cached-price").unwrap(),
+        selector,
+        "cached selector should match the compiled selector"
+    );
+    assert!(SELECTOR_CACHE
+        .lock()
+        .unwrap()
+        .entries
+        .contains_key(".cached-price"));
+    assert!(parse_selector_cached("[[invalid").is_err());
+}
+
+#[test]
+fn test_css_query_select_map_counts() {
+    let map = QueryCSSMap::from([
+        ("price", QueryCSSSelectSet::from([".price"])),
+        ("missing", QueryCSSSelectSet::from([".does-not-exist"])),
+    ]);
+    let html = r#"<html><body><p class="price">1</p><p class="price">2</p></body></html>"#;
+
+    let data = css_query_select_map_counts(html, &build_selectors(map));
+
+    assert_eq!(
+        data.get("price"),
+        Some(&(2, vec!["1".to_string(), "2".to_string()]))
+    );
+    assert_eq!(data.get("missing"), Some(&(0, vec![])));
+}
+
+#[test]
+fn test_parse_xpath_selector() {
+    assert_eq!(
+        parse_xpath_selector("//p[ends-with(@class, 'price')]").unwrap(),
+        "//p[(substring(@class, string-length(@class) - string-length('price') + 1) = 'price')]"
+    );
+    assert_eq!(
+        validate_selector("//p[matches(@class, 'price')]"),
+        Err(SelectorError::UnsupportedXPath {
+            selector: "//p[matches(@class, 'price')]".into(),
+            function: "matches".into()
+        })
+    );
+    assert_eq!(
+        validate_selector("//p[@class="),
+        Err(SelectorError::Invalid("//p[@class=".into()))
+    );
+
+    let map = QueryCSSMap::from([(
+        "price",
+        QueryCSSSelectSet::from(["//p[lower-case(@class)='sale-price']"]),
+    )]);
+    let data = css_query_select_map(
+        r#"<html><body><p class="Sale-Price">1</p><p class="tax">2</p></body></html>"#,
+        &build_selectors(map),
+    );
+
+    assert_eq!(data["price"], vec!["1"]);
+}
+
+#[test]
+fn test_xpath_query_select_map_malformed_html() {
+    let map = QueryCSSMap::from([("item", QueryCSSSelectSet::from(["//li[@class='item']"]))]);
+    let data = css_query_select_map(
+        r#"<html><body><ul><li class="item">First<li class="item">Second<br></ul></body></html>"#,
+        &build_selectors(map),
+    );
+
+    assert_eq!(data["item"], vec!["First"]);
+}
+
+#[test]
+fn test_css_query_select_map_merge_order() {
+    let map = QueryCSSMap::from([(
+        "price",
+        QueryCSSSelectSet::from([".price", "//*[@class='sale']"]),
+    )]);
+    let html = r#"<html><body><p class="sale">2</p><p class="price">1</p></body></html>"#;
+
+    let data = css_query_select_map(html, &build_selectors(map.clone()));
+    let xpath_first = css_query_select_map(
+        html,
+        &build_selectors(map).with_merge_order(SelectorMergeOrder::XPathFirst),
+    );
+
+    assert_eq!(data["price"], vec!["1", "2"]);
+    assert_eq!(xpath_first["price"], vec!["2", "1"]);
+}
+
+#[test]
+fn test_css_query_select_map_first_match() {
+    let selectors = build_selectors_base(HashMap::from([
+        ("price", vec![".price", ".missing-price", ".product-price"]),
+        ("image", vec!["img"]),
+    ]));
+    let html = r#"<html><body><img src="/a.png"><p class="price">1</p><p class="product-price">2</p></body></html>"#;
+
+    let data = css_query_select_map(html, &selectors.clone());
+    let first = css_query_select_map(
+        html,
+        &selectors.with_key_strategy("price", SelectorStrategy::FirstMatch),
+    );
+
+    assert_eq!(data["price"], vec!["1", "2"]);
+    assert_eq!(first["price"], vec!["1"]);
+    assert_eq!(first["image"], vec!["[/a.png]"]);
+}
+
+#[test]
+fn test_extract_tables() {
+    let html = r#"<html><body><table>
+        <thead><tr><th>Name</th><th>Price</th><th>Stock</th></tr></thead>
+        <tbody>
+            <tr><td>Apple</td><td rowspan="2">1.00</td><td>Yes</td></tr>
+            <tr><td>Pear</td><td>No</td></tr>
+            <tr><td colspan="2">Unknown</td></tr>
+        </tbody>
+    </table></body></html>"#;
+
+    let tables = extract_tables(html);
+
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].headers, vec!["Name", "Price", "Stock"]);
+    assert_eq!(tables[0].rows.len(), 3);
+    assert_eq!(tables[0].rows[1]["Name"], "Pear");
+    assert_eq!(tables[0].rows[1]["Price"], "1.00");
+    assert_eq!(tables[0].rows[1]["Stock"], "No");
+    assert_eq!(tables[0].rows[2]["Price"], "Unknown");
+    assert_eq!(tables[0].rows[2]["Stock"], "");
+}
+
+#[test]
+fn test_json_query_select_map() {
+    let body = br#"{"data": {"items": [{"url": "/a", "id": 1}, {"url": "/b", "id": 2}], "next": {"url": "/c"}}}"#;
+
+    let selectors = HashMap::from([
+        ("urls", vec!["$.data.items[*].url".to_string()]),
+        ("ids", vec!["$.data.items[*].id".to_string()]),
+        ("all", vec!["$..url".to_string()]),
+        ("first", vec!["$['data'].items[0]".to_string()]),
+        ("missing", vec!["$.data.missing".to_string()]),
+    ]);
+
+    let data = json_query_select_map(body, &selectors);
+
+    assert_eq!(data["urls"], vec!["/a", "/b"]);
+    assert_eq!(data["ids"], vec!["1", "2"]);
+    assert_eq!(data["all"], vec!["/a", "/b", "/c"]);
+    assert_eq!(data["first"].len(), 1);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&data["first"][0]).unwrap(),
+        serde_json::json!({"url": "/a", "id": 1})
+    );
+    assert!(!data.contains_key("missing"));
+    assert!(json_query_select_map(b"not json", &selectors).is_empty());
+}
+
+#[test]
+fn test_json_path_select() {
+    let value = serde_json::json!({"data": {"items": [{"url": "/a", "id": 1}, {"url": "/b", "id": 2}], "next": {"url": "/c"}}});
+
+    assert_eq!(
+        json_path_select(&value, "$.data.items[1].id"),
+        vec![&serde_json::json!(2)]
+    );
+    assert_eq!(
+        json_path_select(&value, "$['data'].next"),
+        vec![&serde_json::json!({"url": "/c"})]
+    );
+    assert_eq!(
+        json_path_select(&value, "$.data.*.url"),
+        vec![&serde_json::json!("/c")]
+    );
+    assert!(json_path_select(&value, "$.data.missing").is_empty());
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_css_query_select_map_streamed_single() {
+    let map = QueryCSSMap::from([("item", QueryCSSSelectSet::from([".item"]))]);
+    let html = r#"<html><body><p class="item">A</p><p class="item">A</p><p class="item">B</p></body></html>"#;
+    let selectors = build_selectors(map);
+
+    let data = css_query_select_map_streamed(html, &selectors).await;
+
+    assert_eq!(data, css_query_select_stream(html, &selectors).await);
+}